@@ -1,4 +1,18 @@
-// Constantes para os GroupIDs do Zabbix
+use crate::{
+    error::{AppError, Result},
+    models::{
+        common::{Status, TipoEstacao},
+        compare::{ComparableField, DuplicatePolicy, StatusRule},
+        sharepoint::{SharePointApi, SharePointField},
+    },
+    utils::{choice::Choice, normalizer::Normalizer},
+};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Constantes para os GroupIDs do Zabbix (valores padrão)
 pub const STATUS_ATIVO: &str = "54";
 pub const STATUS_DEFEITO: &str = "48";
 pub const STATUS_DISPONIVEL: &str = "122";
@@ -25,4 +39,378 @@ pub const STATION_TYPES: &[&str] = &[
     "UMS300",
     "ERM-x",
     "ERMx",
-];
\ No newline at end of file
+];
+
+// Itens por chamada às APIs do Zabbix e do SharePoint
+pub const ZABBIX_PAGE_SIZE: usize = 500;
+pub const SHAREPOINT_PAGE_SIZE: usize = 500;
+
+// Localização do arquivo de configuração
+pub const CONFIG_ENV_VAR: &str = "ESTACOES_CONFIG";
+pub const CONFIG_DIR_NAME: &str = "estacoes-manager";
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+pub const ALIASES_FILE_NAME: &str = "aliases.toml";
+pub const WAIVERS_FILE_NAME: &str = "waivers.toml";
+pub const HISTORY_DIR_NAME: &str = "history";
+
+/// Configuração carregada em tempo de execução.
+///
+/// Campos ausentes no arquivo assumem os valores padrão, que reproduzem
+/// as constantes acima.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub regional_prefixes: Vec<String>,
+    pub zabbix: ZabbixConfig,
+    pub sharepoint: SharePointConfig,
+    pub station_types: Vec<StationTypeRule>,
+    pub compare: CompareConfig,
+    pub history: HistoryConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ZabbixConfig {
+    /// Mapeamento groupid → status
+    pub status_groups: IndexMap<String, Status>,
+    /// URL da API usada quando nenhum arquivo é informado
+    pub url: Option<String>,
+    /// Quantidade de hosts por chamada a `host.get`
+    pub page_size: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SharePointConfig {
+    /// Mapeamento "Situação do Equipamento" → status
+    pub status_map: IndexMap<String, Status>,
+    /// Cabeçalhos adicionais → campo lógico do registro
+    pub columns: IndexMap<String, SharePointField>,
+    /// Site lido quando nenhum arquivo é informado
+    pub site_url: Option<String>,
+    /// Título (REST) ou ID (Graph) da lista de estações
+    pub list: Option<String>,
+    pub api: SharePointApi,
+    /// Quantidade de itens por página (`$top`)
+    pub page_size: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompareConfig {
+    /// Campos comparados entre as estações correspondentes
+    pub fields: Vec<ComparableField>,
+    /// Confiança mínima (0 a 1) para sugerir uma provável correspondência
+    pub min_confidence: f64,
+    /// Registro que vale quando vários colidem na mesma chave
+    pub duplicate_policy: DuplicatePolicy,
+    /// Arquivo de apelidos (padrão: `aliases.toml` no diretório de
+    /// configuração)
+    pub aliases: Option<PathBuf>,
+    /// Arquivo de isenções (padrão: `waivers.toml` no diretório de
+    /// configuração)
+    pub waivers: Option<PathBuf>,
+    /// Pares de status considerados consistentes
+    pub status_rules: Vec<StatusRule>,
+}
+
+impl Default for CompareConfig {
+    fn default() -> Self {
+        Self {
+            fields: ComparableField::ALL.to_vec(),
+            min_confidence: 0.6,
+            duplicate_policy: DuplicatePolicy::default(),
+            aliases: None,
+            waivers: None,
+            status_rules: StatusRule::defaults(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Diretório dos snapshots (padrão:
+    /// `$XDG_DATA_HOME/estacoes-manager/history`)
+    pub dir: Option<PathBuf>,
+}
+
+/// Regra de identificação de um tipo de estação
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StationTypeRule {
+    /// Nome do tipo, como aceito por `TipoEstacao::from_str`
+    pub name: String,
+    /// Trechos (sem diferenciar maiúsculas) que identificam o tipo no nome
+    pub patterns: Vec<String>,
+    /// Prefixos removidos do ID durante a normalização
+    #[serde(default)]
+    pub id_prefixes: Vec<String>,
+    /// Se os padrões também valem para nomes de grupos do Zabbix
+    #[serde(default = "default_true")]
+    pub match_groups: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            regional_prefixes: REGIONAL_PREFIXES.iter().map(|p| p.to_string()).collect(),
+            zabbix: ZabbixConfig::default(),
+            sharepoint: SharePointConfig::default(),
+            station_types: StationTypeRule::defaults(),
+            compare: CompareConfig::default(),
+            history: HistoryConfig::default(),
+        }
+    }
+}
+
+impl Default for ZabbixConfig {
+    fn default() -> Self {
+        let status_groups = [
+            (STATUS_ATIVO, Status::Ativo),
+            (STATUS_DEFEITO, Status::Defeito),
+            (STATUS_DISPONIVEL, Status::Disponivel),
+            (STATUS_NOMADICO, Status::Nomadico),
+            (STATUS_LITIGIO, Status::Litigio),
+        ]
+        .into_iter()
+        .map(|(id, status)| (id.to_string(), status))
+        .collect();
+
+        Self {
+            status_groups,
+            url: None,
+            page_size: ZABBIX_PAGE_SIZE,
+        }
+    }
+}
+
+impl Default for SharePointConfig {
+    fn default() -> Self {
+        let status_map = SHAREPOINT_STATUS_MAP
+            .iter()
+            .filter_map(|(label, status)| {
+                Status::from_str(status).map(|s| (label.to_string(), s))
+            })
+            .collect();
+
+        Self {
+            status_map,
+            columns: IndexMap::new(),
+            site_url: None,
+            list: None,
+            api: SharePointApi::Rest,
+            page_size: SHAREPOINT_PAGE_SIZE,
+        }
+    }
+}
+
+impl StationTypeRule {
+    fn new(name: &str, patterns: &[&str], id_prefixes: &[&str], match_groups: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            id_prefixes: id_prefixes.iter().map(|p| p.to_string()).collect(),
+            match_groups,
+        }
+    }
+
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("RFeye", &["rfeye"], &["rfeye"], true),
+            Self::new("MIAer", &["miaer"], &["miaer-"], true),
+            Self::new("CelWireless RMU", &["cwsm"], &["cwsm"], false),
+            Self::new("UMS300", &["ums"], &["ums"], false),
+            Self::new("ERM-x", &["erm"], &["ermx"], true),
+        ]
+    }
+
+    pub fn tipo(&self) -> TipoEstacao {
+        TipoEstacao::from_str(&self.name)
+    }
+
+    fn matches(&self, text_lower: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|p| text_lower.contains(&p.to_lowercase()))
+    }
+}
+
+impl Config {
+    /// Carrega a configuração a partir do primeiro local encontrado:
+    /// caminho explícito (`--config`), variável `ESTACOES_CONFIG` ou
+    /// `$XDG_CONFIG_HOME/estacoes-manager/config.toml`. Sem arquivo,
+    /// usa os valores padrão.
+    pub fn load(explicit: Option<&Path>) -> Result<Self> {
+        match Self::resolve_path(explicit) {
+            Some(path) => Self::from_file(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    fn resolve_path(explicit: Option<&Path>) -> Option<PathBuf> {
+        if let Some(path) = explicit {
+            return Some(path.to_path_buf());
+        }
+
+        if let Some(path) = std::env::var_os(CONFIG_ENV_VAR).filter(|p| !p.is_empty()) {
+            return Some(PathBuf::from(path));
+        }
+
+        Self::config_dir()
+            .map(|dir| dir.join(CONFIG_FILE_NAME))
+            .filter(|path| path.exists())
+    }
+
+    /// `$XDG_CONFIG_HOME/estacoes-manager` (ou `~/.config/estacoes-manager`)
+    fn config_dir() -> Option<PathBuf> {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+            .map(|dir| dir.join(CONFIG_DIR_NAME))
+    }
+
+    /// Diretório dos snapshots de `compare --save`
+    pub fn history_dir(&self) -> Option<PathBuf> {
+        self.history.dir.clone().or_else(|| {
+            std::env::var_os("XDG_DATA_HOME")
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from)
+                .or_else(|| {
+                    std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("share"))
+                })
+                .map(|dir| dir.join(CONFIG_DIR_NAME).join(HISTORY_DIR_NAME))
+        })
+    }
+
+    /// Caminho do arquivo de apelidos
+    pub fn aliases_path(&self) -> Option<PathBuf> {
+        self.compare
+            .aliases
+            .clone()
+            .or_else(|| Self::config_dir().map(|dir| dir.join(ALIASES_FILE_NAME)))
+    }
+
+    /// Caminho do arquivo de isenções
+    pub fn waivers_path(&self) -> Option<PathBuf> {
+        self.compare
+            .waivers
+            .clone()
+            .or_else(|| Self::config_dir().map(|dir| dir.join(WAIVERS_FILE_NAME)))
+    }
+
+    /// Lê um arquivo TOML ou JSON (decidido pela extensão)
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            AppError::Config(format!("{}: {}", path.display(), e))
+        })?;
+
+        let is_json = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("json"))
+            .unwrap_or(false);
+
+        if is_json {
+            Self::from_json_str(&content)
+        } else {
+            Self::from_toml_str(&content)
+        }
+    }
+
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        toml::from_str(content).map_err(|e| AppError::Config(e.to_string()))
+    }
+
+    pub fn from_json_str(content: &str) -> Result<Self> {
+        serde_json::from_str(content).map_err(|e| AppError::Config(e.to_string()))
+    }
+
+    /// Status associado a um groupid do Zabbix
+    pub fn zabbix_status(&self, groupid: &str) -> Option<Status> {
+        self.zabbix.status_groups.get(groupid).cloned()
+    }
+
+    /// Status associado a uma "Situação do Equipamento" do SharePoint.
+    /// A comparação ignora maiúsculas e acentos; valores fora do mapa
+    /// caem no vocabulário reconhecido por `Status::from_str`.
+    pub fn sharepoint_status(&self, situacao: &str) -> Option<Status> {
+        let wanted = Normalizer::normalize_text(situacao.trim()).to_lowercase();
+
+        self.sharepoint
+            .status_map
+            .iter()
+            .find(|(label, _)| Normalizer::normalize_text(label).to_lowercase() == wanted)
+            .map(|(_, status)| status.clone())
+            .or_else(|| Status::from_str(situacao.trim()))
+    }
+
+    /// Verifica se o nome de um grupo corresponde a uma regional
+    pub fn is_regional(&self, name: &str) -> bool {
+        self.regional_prefixes.iter().any(|p| name.starts_with(p.as_str()))
+    }
+
+    /// Identifica o tipo de estação pelo nome ou ID
+    pub fn tipo_for_name(&self, name: &str) -> Option<TipoEstacao> {
+        let lower = name.to_lowercase();
+        self.station_types
+            .iter()
+            .find(|rule| rule.matches(&lower))
+            .map(StationTypeRule::tipo)
+    }
+
+    /// Identifica o tipo de estação pelo nome de um grupo do Zabbix
+    pub fn tipo_for_group(&self, group_name: &str) -> Option<TipoEstacao> {
+        let lower = group_name.to_lowercase();
+        self.station_types
+            .iter()
+            .filter(|rule| rule.match_groups)
+            .find(|rule| rule.matches(&lower))
+            .map(StationTypeRule::tipo)
+    }
+
+    /// Prefixos de ID removidos pela normalização, na ordem configurada
+    pub fn id_prefixes(&self) -> impl Iterator<Item = &str> {
+        self.station_types
+            .iter()
+            .flat_map(|rule| rule.id_prefixes.iter().map(String::as_str))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matches_constants() {
+        let config = Config::default();
+        assert_eq!(config.zabbix_status(STATUS_DEFEITO), Some(Status::Defeito));
+        assert_eq!(config.sharepoint_status("Disponível"), Some(Status::Disponivel));
+        assert_eq!(config.sharepoint_status("manutencao"), Some(Status::Manutencao));
+        assert_eq!(config.tipo_for_name("RFeye002300"), Some(TipoEstacao::RFeye));
+        assert!(config.is_regional("GR01"));
+    }
+
+    #[test]
+    fn test_partial_toml_keeps_defaults() {
+        let config = Config::from_toml_str(
+            r#"
+            regional_prefixes = ["GR", "UO", "ER"]
+
+            [zabbix.status_groups]
+            "54" = "Ativo"
+            "200" = "Manutencao"
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.is_regional("ER05"));
+        assert_eq!(config.zabbix_status("200"), Some(Status::Manutencao));
+        assert_eq!(config.zabbix_status(STATUS_DEFEITO), None);
+        assert_eq!(config.sharepoint_status("Ativo"), Some(Status::Ativo));
+        assert_eq!(config.station_types.len(), StationTypeRule::defaults().len());
+    }
+}
@@ -1,29 +1,57 @@
+use crate::i18n::tf;
+use std::fmt;
 use thiserror::Error;
 
+/// Mensagens vêm do catálogo do idioma atual (`error.*`)
 #[derive(Error, Debug)]
 pub enum AppError {
-    #[error("Erro ao ler arquivo: {0}")]
     FileRead(#[from] std::io::Error),
     
-    #[error("Erro ao parsear JSON: {0}")]
     JsonParse(#[from] serde_json::Error),
     
-    #[error("Erro ao parsear CSV: {0}")]
     CsvParse(#[from] csv::Error),
     
-    #[error("Status inválido: {0}")]
+    MissingColumns(Vec<String>),
+    
     InvalidStatus(String),
     
-    #[error("Regional não encontrada para host: {0}")]
     RegionalNotFound(String),
     
-    #[error("Discrepância encontrada: {0}")]
+    Http(String),
+    
+    ZabbixApi(String),
+    
+    Config(String),
+    
+    Template(String),
+    
     Discrepancy(String),
     
-    #[error("Erro genérico: {0}")]
     Generic(String),
 }
 
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (key, detail): (&str, &dyn fmt::Display) = match self {
+            AppError::FileRead(e) => ("error.file_read", e),
+            AppError::JsonParse(e) => ("error.json_parse", e),
+            AppError::CsvParse(e) => ("error.csv_parse", e),
+            AppError::MissingColumns(columns) => {
+                return f.write_str(&tf("error.missing_columns", &[("columns", &columns.join(", "))]));
+            }
+            AppError::InvalidStatus(s) => ("error.invalid_status", s),
+            AppError::RegionalNotFound(s) => ("error.regional_not_found", s),
+            AppError::Http(s) => ("error.http", s),
+            AppError::ZabbixApi(s) => ("error.zabbix_api", s),
+            AppError::Config(s) => ("error.config", s),
+            AppError::Template(s) => ("error.template", s),
+            AppError::Discrepancy(s) => ("error.discrepancy", s),
+            AppError::Generic(s) => ("error.generic", s),
+        };
+        f.write_str(&tf(key, &[("detail", detail)]))
+    }
+}
+
 pub type Result<T> = std::result::Result<T, AppError>;
 
 impl From<String> for AppError {
@@ -36,4 +64,4 @@ impl From<&str> for AppError {
     fn from(s: &str) -> Self {
         AppError::Generic(s.to_string())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,223 @@
+//! Catálogos de mensagens em pt-BR e inglês, embutidos no binário a partir
+//! de `locales/*.toml`. O idioma vem de `--lang` ou do locale do sistema;
+//! chave ausente no catálogo escolhido cai no pt-BR e, por fim, na própria
+//! chave.
+
+use crate::utils::choice::Choice;
+use chrono::{DateTime, NaiveDate, TimeZone};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+type Catalog = HashMap<String, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Lang {
+    #[default]
+    PtBr,
+    En,
+}
+
+impl Choice for Lang {
+    const ALL: &'static [Self] = &[Lang::PtBr, Lang::En];
+
+    fn name(self) -> &'static str {
+        self.code()
+    }
+
+    fn aliases(self) -> &'static [&'static str] {
+        match self {
+            Lang::PtBr => &["pt"],
+            Lang::En => &[],
+        }
+    }
+}
+
+impl Lang {
+    /// Idioma de um locale POSIX ("en_US.UTF-8", "pt_BR", "C"...)
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let language = locale.split(['_', '-', '.', '@']).next()?;
+        match language.to_ascii_lowercase().as_str() {
+            "pt" => Some(Self::PtBr),
+            "en" => Some(Self::En),
+            _ => None,
+        }
+    }
+
+    /// Idioma do ambiente: a primeira variável definida entre `LC_ALL`,
+    /// `LC_MESSAGES` e `LANG` decide, como no POSIX; pt-BR se nenhuma
+    /// indicar um idioma conhecido
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_locale(&value))
+            .unwrap_or_default()
+    }
+
+    /// Código BCP 47, usado no atributo `lang` do HTML
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::PtBr => "pt-BR",
+            Self::En => "en",
+        }
+    }
+
+    fn catalog(self) -> &'static Catalog {
+        static PT_BR: OnceLock<Catalog> = OnceLock::new();
+        static EN: OnceLock<Catalog> = OnceLock::new();
+        match self {
+            Self::PtBr => PT_BR.get_or_init(|| parse(include_str!("../locales/pt-BR.toml"))),
+            Self::En => EN.get_or_init(|| parse(include_str!("../locales/en.toml"))),
+        }
+    }
+}
+
+/// Achata as tabelas do TOML em chaves "secao.chave"
+fn parse(source: &str) -> Catalog {
+    fn flatten(prefix: &str, table: &toml::Table, catalog: &mut Catalog) {
+        for (key, value) in table {
+            let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+            match value {
+                toml::Value::Table(table) => flatten(&key, table, catalog),
+                toml::Value::String(text) => {
+                    catalog.insert(key, text.clone());
+                }
+                other => panic!("catálogo: valor não textual em {}: {}", key, other),
+            }
+        }
+    }
+
+    let table: toml::Table = source.parse().expect("catálogo de mensagens inválido");
+    let mut catalog = Catalog::new();
+    flatten("", &table, &mut catalog);
+    catalog
+}
+
+thread_local! {
+    // Por thread para que os testes, executados em paralelo, não
+    // interfiram uns nos outros; o binário só define o idioma na principal
+    static CURRENT: Cell<Lang> = Cell::new(Lang::default());
+}
+
+/// Define o idioma das mensagens geradas nesta thread
+pub fn set_lang(lang: Lang) {
+    CURRENT.with(|current| current.set(lang));
+}
+
+pub fn lang() -> Lang {
+    CURRENT.with(Cell::get)
+}
+
+/// Executa `f` com as mensagens em `lang`, restaurando o idioma anterior
+pub fn with_lang<T>(lang: Lang, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|current| current.replace(lang));
+    let result = f();
+    set_lang(previous);
+    result
+}
+
+/// Texto da chave só no idioma atual, sem recorrer ao pt-BR
+pub fn lookup(key: &str) -> Option<&'static str> {
+    lang().catalog().get(key).map(String::as_str)
+}
+
+/// Mensagem da chave no idioma atual
+pub fn t(key: &str) -> String {
+    lookup(key)
+        .or_else(|| Lang::PtBr.catalog().get(key).map(String::as_str))
+        .unwrap_or(key)
+        .to_string()
+}
+
+/// Mensagem com os marcadores `{nome}` substituídos pelos argumentos
+pub fn tf(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter().fold(t(key), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), &value.to_string())
+    })
+}
+
+/// Inteiro com separador de milhar ("1.234" ou "1,234")
+pub fn number(value: usize) -> String {
+    let digits = value.to_string();
+    let separator = t("format.thousands");
+    let mut text = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            text.push_str(&separator);
+        }
+        text.push(digit);
+    }
+    text
+}
+
+/// Percentual com o separador decimal do idioma ("12,5%" ou "12.5%")
+pub fn percent(value: f64, decimals: usize) -> String {
+    let text = format!("{:.*}", decimals, value);
+    format!("{}%", text.replace('.', &t("format.decimal")))
+}
+
+/// Data e hora no formato do idioma
+pub fn datetime<Tz: TimeZone>(value: &DateTime<Tz>) -> String
+where
+    Tz::Offset: Display,
+{
+    value.format(&t("format.datetime")).to_string()
+}
+
+/// Data e hora sem os segundos
+pub fn datetime_short<Tz: TimeZone>(value: &DateTime<Tz>) -> String
+where
+    Tz::Offset: Display,
+{
+    value.format(&t("format.datetime_short")).to_string()
+}
+
+pub fn date(value: NaiveDate) -> String {
+    value.format(&t("format.date")).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_catalogs_and_formats() {
+        // Os dois catálogos têm as mesmas chaves, fora a ajuda dos
+        // argumentos em pt-BR, que vem dos comentários de documentação; a
+        // dos valores aceitos está nos dois
+        let keys = |lang: Lang| -> BTreeSet<&String> {
+            lang.catalog()
+                .keys()
+                .filter(|k| !k.starts_with("help.") || k.starts_with("help.values."))
+                .collect()
+        };
+        let (pt, en) = (keys(Lang::PtBr), keys(Lang::En));
+        assert_eq!(pt.symmetric_difference(&en).collect::<Vec<_>>(), Vec::<&&String>::new());
+
+        assert_eq!(Lang::from_locale("en_US.UTF-8"), Some(Lang::En));
+        assert_eq!(Lang::from_locale("pt_BR"), Some(Lang::PtBr));
+        assert_eq!(Lang::from_locale("C"), None);
+
+        let day = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+        let moment = day.and_hms_opt(14, 7, 9).unwrap().and_utc();
+
+        set_lang(Lang::PtBr);
+        assert_eq!(number(1234567), "1.234.567");
+        assert_eq!(percent(12.345, 1), "12,3%");
+        assert_eq!(datetime(&moment), "05/03/2024 14:07:09");
+        assert_eq!(tf("report.generated_at", &[("date", &datetime(&moment))]), "Gerado em: 05/03/2024 14:07:09");
+
+        set_lang(Lang::En);
+        assert_eq!(number(1234567), "1,234,567");
+        assert_eq!(number(999), "999");
+        assert_eq!(percent(12.345, 1), "12.3%");
+        assert_eq!(date(day), "03/05/2024");
+        assert_eq!(tf("report.generated_at", &[("date", &datetime(&moment))]), "Generated: 03/05/2024 02:07:09 PM");
+        assert_eq!(t("missing.key"), "missing.key");
+        set_lang(Lang::PtBr);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod config;
 pub mod error;
+pub mod i18n;
 pub mod models;
 pub mod parsers;
 pub mod reports;
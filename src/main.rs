@@ -1,26 +1,65 @@
-use anyhow::Result;
-use clap::{Parser, Subcommand};
+use anyhow::{bail, Context, Result};
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
+use clap::{Args, Command, CommandFactory, FromArgMatches, Parser, Subcommand};
 use colored::Colorize;
 use estacoes_manager::{
-    parsers::{csv_parser::CsvParser, zabbix_parser::ZabbixParser},
-    reports::{discrepancy::DiscrepancyReport, summary::SummaryReport},
+    config::Config,
+    i18n::{self, t, tf, Lang},
+    models::{
+        common::{DataSource, EstacaoInfo, Status},
+        compare::{ComparableField, DuplicatePolicy},
+        sharepoint::{SharePointApi, SharePointRecord},
+        zabbix::ZabbixHost,
+    },
+    parsers::{
+        csv_parser::{ColumnMapping, CsvParser},
+        diagnostic::{InvalidRowPolicy, ParseDiagnostic, ParseMode},
+        zabbix_parser::ZabbixParser,
+    },
+    reports::{
+        discrepancy::DiscrepancyReport, history::HistoryReport, html::HtmlReport, json::JsonReport,
+        markdown::MarkdownRenderer, model::Report, render::{ReportRenderer, TextRenderer},
+        template::TemplateRenderer, summary::SummaryReport, table::TableReport, trend::TrendReport, OutputFormat,
+    },
     services::{
-        aggregator::Aggregator, comparator::Comparator, data_loader::DataLoader,
+        aggregator::Aggregator,
+        aliases::{Alias, Aliases},
+        comparator::Comparator,
+        data_loader::DataLoader,
+        filter::StationFilter,
+        history::{Snapshot, SnapshotStore},
+        trend::{Trend, TrendPeriod},
+        sharepoint_client::SharePointClient,
+        thresholds::{Threshold, ThresholdCheck},
+        waivers::Waivers,
+        zabbix_client::{ZabbixAuth, ZabbixClient},
     },
+    utils::choice::Choice,
 };
-use std::path::PathBuf;
-use tracing::info;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use tracing::{info, warn};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 #[derive(Parser)]
 #[command(name = "estacoes-manager")]
 #[command(about = "Gerenciador de Estações - Comparação Zabbix/SharePoint", long_about = None)]
 struct Cli {
+    /// Arquivo de configuração (TOML ou JSON)
+    #[arg(short, long, global = true)]
+    config: Option<PathBuf>,
+    
+    /// Idioma das mensagens e relatórios (padrão: locale do sistema)
+    #[arg(long, value_parser = choice::<Lang>("lang"), global = true)]
+    lang: Option<Lang>,
+    
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 enum Commands {
     /// Analisa dados do Zabbix
     Zabbix {
@@ -28,57 +67,533 @@ enum Commands {
         #[arg(short, long)]
         file: PathBuf,
         
+        #[command(flatten)]
+        filter: FilterArgs,
+        
         /// Gera relatório resumido
         #[arg(short, long)]
         summary: bool,
+        
+        /// Formato da saída
+        #[arg(long, value_parser = choice::<OutputFormat>("format"), default_value = "text")]
+        format: OutputFormat,
+        
+        /// Template Jinja com layout próprio (ignora --format)
+        #[arg(long)]
+        template: Option<PathBuf>,
     },
     
     /// Analisa dados do SharePoint
     SharePoint {
-        /// Arquivo CSV do SharePoint
+        /// Arquivo CSV do SharePoint ou resposta JSON salva da API
         #[arg(short, long)]
-        file: PathBuf,
+        file: Option<PathBuf>,
+        
+        #[command(flatten)]
+        source: SharePointSource,
+        
+        #[command(flatten)]
+        filter: SharePointFilterArgs,
         
         /// Gera relatório resumido
         #[arg(short, long)]
         summary: bool,
+        
+        /// Formato da saída
+        #[arg(long, value_parser = choice::<OutputFormat>("format"), default_value = "text")]
+        format: OutputFormat,
+        
+        /// Template Jinja com layout próprio (ignora --format)
+        #[arg(long)]
+        template: Option<PathBuf>,
     },
     
     /// Compara dados do Zabbix com SharePoint
     Compare {
-        /// Arquivo JSON do Zabbix
-        #[arg(short = 'z', long)]
-        zabbix: PathBuf,
+        #[command(flatten)]
+        zabbix: ZabbixSource,
         
-        /// Arquivo CSV do SharePoint
+        /// Arquivo CSV do SharePoint ou resposta JSON salva da API
         #[arg(short = 's', long)]
-        sharepoint: PathBuf,
+        sharepoint: Option<PathBuf>,
+        
+        #[command(flatten)]
+        sharepoint_api: SharePointSource,
+        
+        #[command(flatten)]
+        filter: SharePointFilterArgs,
+        
+        /// Campos comparados (padrão: todos, ou `compare.fields` da configuração)
+        #[arg(long, value_parser = choice::<ComparableField>("field"), value_delimiter = ',')]
+        fields: Vec<ComparableField>,
+        
+        /// Registro comparado quando há chaves duplicadas
+        #[arg(long, value_parser = choice::<DuplicatePolicy>("duplicate_policy"))]
+        duplicate_policy: Option<DuplicatePolicy>,
+        
+        /// Arquivo de apelidos (padrão: `compare.aliases` da configuração)
+        #[arg(long)]
+        aliases: Option<PathBuf>,
+        
+        /// Arquivo de isenções (padrão: `compare.waivers` da configuração)
+        #[arg(long)]
+        waivers: Option<PathBuf>,
+        
+        /// Grava entradas e resultado no histórico
+        #[arg(long)]
+        save: bool,
+        
+        /// Diretório do histórico (padrão: `history.dir` da configuração)
+        #[arg(long)]
+        history_dir: Option<PathBuf>,
         
         /// Arquivo de saída para o relatório
         #[arg(short, long)]
         output: Option<PathBuf>,
+        
+        /// Exporta as discrepâncias em planilha (.csv ou .xlsx, uma aba por categoria)
+        #[arg(long)]
+        export: Option<PathBuf>,
+        
+        /// Formato da saída
+        #[arg(long, value_parser = choice::<OutputFormat>("format"), default_value = "text")]
+        format: OutputFormat,
+        
+        /// Template Jinja com layout próprio (ignora --format)
+        #[arg(long)]
+        template: Option<PathBuf>,
+        
+        /// Falha com código de saída próprio quando as discrepâncias passam
+        /// do limite: `any`, uma categoria (`status`) ou uma categoria com
+        /// máximo (`only_in_zabbix>5`); repetível ou separado por vírgulas.
+        ///
+        /// Códigos de saída: 3 para `any`; 10 a 18 por categoria (status,
+        /// regional, field, broken_link, ip, duplicate, probable_match,
+        /// only_in_zabbix, only_in_sharepoint). Com vários limites
+        /// violados, vale o primeiro informado. Um resumo JSON de uma
+        /// linha vai para o stderr.
+        #[arg(long, value_delimiter = ',')]
+        fail_on: Vec<Threshold>,
     },
+    
+    /// Registra no arquivo de apelidos uma correspondência confirmada
+    Link {
+        /// hostid ou nome do host no Zabbix
+        #[arg(short = 'z', long)]
+        zabbix: String,
+        
+        /// "ID de rede" no SharePoint
+        #[arg(short = 's', long)]
+        sharepoint: String,
+        
+        /// Observação gravada junto do apelido
+        #[arg(short, long)]
+        note: Option<String>,
+        
+        /// Arquivo de apelidos (padrão: `compare.aliases` da configuração)
+        #[arg(long)]
+        aliases: Option<PathBuf>,
+    },
+    
+    /// Consulta o histórico de comparações gravadas com `compare --save`
+    History {
+        #[command(subcommand)]
+        command: HistoryCommand,
+        
+        /// Diretório do histórico (padrão: `history.dir` da configuração)
+        #[arg(long, global = true)]
+        history_dir: Option<PathBuf>,
+    },
+    
+    /// Evolução das contagens por status e regional ao longo dos snapshots
+    Trend {
+        /// Origem das estações contadas
+        #[arg(long, value_parser = choice::<DataSource>("source"), default_value = "zabbix")]
+        source: DataSource,
+        
+        /// Usa todos os snapshots ou só o último de cada dia/mês
+        #[arg(long, value_parser = choice::<TrendPeriod>("period"), default_value = "all")]
+        period: TrendPeriod,
+        
+        #[command(flatten)]
+        filter: SharePointFilterArgs,
+        
+        /// Exporta as séries em CSV
+        #[arg(long)]
+        csv: Option<PathBuf>,
+        
+        /// Diretório do histórico (padrão: `history.dir` da configuração)
+        #[arg(long)]
+        history_dir: Option<PathBuf>,
+        
+        /// Formato da saída
+        #[arg(long, value_parser = choice::<OutputFormat>("format"), default_value = "text")]
+        format: OutputFormat,
+        
+        /// Template Jinja com layout próprio (ignora --format)
+        #[arg(long)]
+        template: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryCommand {
+    /// Lista os snapshots gravados
+    List,
+    
+    /// Discrepâncias novas, resolvidas e persistentes entre dois snapshots
+    Diff {
+        /// Snapshot inicial (padrão: o penúltimo)
+        from: Option<String>,
+        
+        /// Snapshot final (padrão: o último)
+        to: Option<String>,
+        
+        /// Formato da saída
+        #[arg(long, value_parser = choice::<OutputFormat>("format"), default_value = "text")]
+        format: OutputFormat,
+        
+        /// Template Jinja com layout próprio (ignora --format)
+        #[arg(long)]
+        template: Option<PathBuf>,
+    },
+}
+
+/// Recorte das estações, aplicado antes da agregação e da comparação
+#[derive(Args)]
+struct FilterArgs {
+    /// Só estas regionais (ex.: GR01,GR02)
+    #[arg(long, value_delimiter = ',')]
+    regional: Vec<String>,
+    
+    /// Só estes status
+    #[arg(long, value_parser = parse_status, value_delimiter = ',')]
+    status: Vec<Status>,
+    
+    /// Só estes tipos de estação (ex.: RFeye,ERM-x)
+    #[arg(long, value_delimiter = ',')]
+    tipo: Vec<String>,
+    
+    /// Só IDs ou nomes que casam com o padrão (`*` e `?`, ex.: RFeye0023*)
+    #[arg(long, value_delimiter = ',')]
+    id_pattern: Vec<String>,
+}
+
+impl FilterArgs {
+    fn filter(&self) -> StationFilter {
+        StationFilter {
+            regionals: self.regional.clone(),
+            statuses: self.status.clone(),
+            tipos: self.tipo.clone(),
+            ufs: Vec::new(),
+            id_patterns: self.id_pattern.clone(),
+        }
+    }
+}
+
+/// Recorte com a UF, que só o SharePoint informa; por isso o subcomando
+/// `zabbix` usa `FilterArgs`, sem `--uf`
+#[derive(Args)]
+struct SharePointFilterArgs {
+    #[command(flatten)]
+    common: FilterArgs,
+    
+    /// Só estas UFs ("Local:UF" do SharePoint)
+    #[arg(long, value_delimiter = ',')]
+    uf: Vec<String>,
+}
+
+impl SharePointFilterArgs {
+    fn filter(&self) -> StationFilter {
+        StationFilter {
+            ufs: self.uf.clone(),
+            ..self.common.filter()
+        }
+    }
+}
+
+#[derive(Args)]
+struct ZabbixSource {
+    /// Arquivo JSON do Zabbix
+    #[arg(short = 'z', long = "zabbix", conflicts_with = "zabbix_url")]
+    file: Option<PathBuf>,
+    
+    /// URL da API do Zabbix (dispensa a exportação manual)
+    #[arg(long)]
+    zabbix_url: Option<String>,
+    
+    /// Token de API do Zabbix
+    #[arg(long, env = "ZABBIX_API_TOKEN", hide_env_values = true)]
+    zabbix_token: Option<String>,
+    
+    /// Usuário da API do Zabbix
+    #[arg(long, env = "ZABBIX_USER")]
+    zabbix_user: Option<String>,
+    
+    /// Senha da API do Zabbix
+    #[arg(long, env = "ZABBIX_PASSWORD", hide_env_values = true)]
+    zabbix_password: Option<String>,
+}
+
+impl ZabbixSource {
+    /// Lê os hosts do arquivo exportado ou, sem arquivo, da API
+    fn load_hosts(&self, config: &Config) -> Result<Vec<ZabbixHost>> {
+        if let Some(file) = &self.file {
+            let data = DataLoader::load_file(file)?;
+            let export = ZabbixParser::parse_export(&data)?;
+            if let Some(version) = export.version {
+                info!("{}", tf("log.zabbix_export_version", &[("version", &version)]));
+            }
+            return Ok(export.hosts);
+        }
+        
+        let url = self
+            .zabbix_url
+            .as_deref()
+            .or(config.zabbix.url.as_deref())
+            .with_context(|| t("cli.need_zabbix"))?;
+        
+        let auth = match (&self.zabbix_token, &self.zabbix_user, &self.zabbix_password) {
+            (Some(token), _, _) => ZabbixAuth::Token(token.clone()),
+            (None, Some(user), Some(password)) => ZabbixAuth::Password {
+                user: user.clone(),
+                password: password.clone(),
+            },
+            _ => bail!(t("cli.need_zabbix_auth")),
+        };
+        
+        info!("{}", tf("log.zabbix_api", &[("url", &url)]));
+        let mut client = ZabbixClient::new(url).with_page_size(config.zabbix.page_size);
+        client.login(&auth)?;
+        let hosts = client.fetch_hosts();
+        // A sessão é encerrada também quando a busca falha; uma falha no
+        // logout não invalida os hosts já lidos
+        if let Err(e) = client.logout() {
+            warn!("{}", tf("log.zabbix_logout", &[("error", &e)]));
+        }
+        Ok(hosts?)
+    }
+}
+
+#[derive(Args)]
+struct SharePointSource {
+    /// URL do site do SharePoint (ou do site no Microsoft Graph)
+    #[arg(long)]
+    sharepoint_site: Option<String>,
+    
+    /// Título (REST) ou ID (Graph) da lista de estações
+    #[arg(long)]
+    sharepoint_list: Option<String>,
+    
+    /// API usada para ler a lista
+    #[arg(long, value_parser = choice::<SharePointApi>("sharepoint_api"))]
+    sharepoint_api: Option<SharePointApi>,
+    
+    /// Token de acesso (Bearer) do SharePoint/Graph
+    #[arg(long, env = "SHAREPOINT_TOKEN", hide_env_values = true)]
+    sharepoint_token: Option<String>,
+    
+    /// Modo tolerante: linhas inválidas viram diagnósticos em vez de abortar
+    #[arg(long, value_parser = choice::<InvalidRowPolicy>("lenient"), num_args = 0..=1, require_equals = true, default_missing_value = "unknown")]
+    lenient: Option<InvalidRowPolicy>,
+}
+
+impl SharePointSource {
+    fn mode(&self) -> ParseMode {
+        self.lenient.map(ParseMode::Lenient).unwrap_or_default()
+    }
+    
+    /// Lê as estações e os diagnósticos de leitura do modo tolerante
+    fn load_estacoes(
+        &self,
+        file: Option<&Path>,
+        config: &Config,
+    ) -> Result<(Vec<EstacaoInfo>, Vec<ParseDiagnostic>)> {
+        let (records, mut diagnostics) = self.load_records(file, config)?;
+        let (estacoes, row_diagnostics) =
+            CsvParser::to_estacao_info_with(records, config, self.mode())?;
+        diagnostics.extend(row_diagnostics);
+        
+        for diagnostic in &diagnostics {
+            warn!("{}", tf("log.sharepoint_diagnostic", &[("diagnostic", diagnostic)]));
+        }
+        Ok((estacoes, diagnostics))
+    }
+    
+    /// Lê os registros do CSV, de uma resposta JSON salva ou, sem arquivo,
+    /// da API
+    fn load_records(
+        &self,
+        file: Option<&Path>,
+        config: &Config,
+    ) -> Result<(Vec<SharePointRecord>, Vec<ParseDiagnostic>)> {
+        if let Some(file) = file {
+            let data = DataLoader::load_file(file)?;
+            let is_json = file
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+            return Ok(if is_json {
+                let columns = ColumnMapping::from_config(config);
+                (SharePointClient::parse_saved_response(&data, &columns)?, Vec::new())
+            } else {
+                CsvParser::parse_with(&data, config, self.mode())?
+            });
+        }
+        
+        let site = self
+            .sharepoint_site
+            .as_deref()
+            .or(config.sharepoint.site_url.as_deref())
+            .with_context(|| t("cli.need_sharepoint"))?;
+        let list = self
+            .sharepoint_list
+            .as_deref()
+            .or(config.sharepoint.list.as_deref())
+            .with_context(|| t("cli.need_sharepoint_list"))?;
+        let api = self.sharepoint_api.unwrap_or(config.sharepoint.api);
+        
+        info!("{}", tf("log.sharepoint_list", &[("list", &list), ("site", &site)]));
+        let mut client = SharePointClient::new(api, site, list)
+            .with_page_size(config.sharepoint.page_size)
+            .with_columns(ColumnMapping::from_config(config));
+        if let Some(token) = &self.sharepoint_token {
+            client = client.with_token(token);
+        }
+        Ok((client.fetch_records()?, Vec::new()))
+    }
+}
+
+/// Valores aceitos de um enum da biblioteca, com a ajuda de cada um em
+/// `help.values.<kind>.<valor>` no idioma atual
+fn choice<T: Choice + Send + Sync>(kind: &str) -> impl TypedValueParser<Value = T> {
+    let values = T::ALL.iter().map(|value| {
+        let help = i18n::lookup(&format!("help.values.{}.{}", kind, value.name()));
+        PossibleValue::new(value.name())
+            .aliases(value.aliases().iter().copied())
+            .help(help)
+    });
+    PossibleValuesParser::new(values).map(|name| T::from_name(&name).expect("valor aceito pelo clap"))
+}
+
+fn parse_status(value: &str) -> std::result::Result<Status, String> {
+    Status::from_str(value).ok_or_else(|| tf("cli.invalid_status", &[("value", &value)]))
+}
+
+/// Relatório das estações de uma origem nos formatos não textuais; `None`
+/// para o texto, que é impresso pelo próprio comando
+fn stations_report(
+    source: DataSource,
+    estacoes: &[EstacaoInfo],
+    diagnostics: &[ParseDiagnostic],
+    unattributed: &[ParseDiagnostic],
+    format: OutputFormat,
+    template: Option<&Path>,
+) -> Result<Option<String>> {
+    let aggregated = Aggregator::group_by_status_and_regional(estacoes);
+    let title = tf("cli.stations_title", &[("source", &source)]);
+    let report = match (template, format) {
+        (None, OutputFormat::Text) => return Ok(None),
+        (Some(path), _) => TemplateRenderer::from_file(path)?
+            .with_data(&aggregated)?
+            .render(&SummaryReport::model(&aggregated))?,
+        (None, OutputFormat::Json) => JsonReport::stations(source, estacoes, &aggregated, diagnostics, unattributed)?,
+        (None, OutputFormat::Html) => HtmlReport::summary(&title, &aggregated),
+        (None, OutputFormat::Markdown) => MarkdownRenderer.render(&SummaryReport::model(&aggregated))?,
+    };
+    Ok(Some(report))
+}
+
+/// Relatório de `trend` e `history diff` no formato pedido; `json` é o
+/// documento de `data`, que também alimenta o template
+fn model_report<T: Serialize>(
+    report: &Report,
+    data: &T,
+    json: impl FnOnce() -> Result<String>,
+    format: OutputFormat,
+    template: Option<&Path>,
+) -> Result<String> {
+    let text = match (template, format) {
+        (Some(path), _) => TemplateRenderer::from_file(path)?.with_data(data)?.render(report)?,
+        (None, OutputFormat::Text) => TextRenderer::render_text(report),
+        (None, OutputFormat::Json) => json()?,
+        (None, OutputFormat::Html) => HtmlReport.render(report)?,
+        (None, OutputFormat::Markdown) => MarkdownRenderer.render(report)?,
+    };
+    Ok(text)
+}
+
+/// `--lang` da linha de comando ou, sem ele, o idioma do locale
+fn requested_lang() -> Lang {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .enumerate()
+        .find_map(|(i, arg)| match arg.strip_prefix("--lang") {
+            Some("") => args.get(i + 1).map(String::as_str),
+            Some(value) => value.strip_prefix('='),
+            None => None,
+        })
+        .and_then(Lang::from_name)
+        .unwrap_or_else(Lang::from_env)
 }
 
-fn main() -> Result<()> {
+/// Aplica ao comando e aos subcomandos as traduções `help.*` do idioma
+/// atual; o que faltar fica com os comentários de documentação
+fn localize(mut command: Command, prefix: &str) -> Command {
+    if let Some(about) = i18n::lookup(&format!("{}.about", prefix)) {
+        command = command.about(about);
+    }
+    let ids: Vec<String> = command.get_arguments().map(|arg| arg.get_id().to_string()).collect();
+    for id in ids {
+        if let Some(help) = i18n::lookup(&format!("{}.{}", prefix, id)) {
+            // Sem tradução da ajuda longa, vale a curta
+            let long_help = i18n::lookup(&format!("{}.long.{}", prefix, id));
+            command = command.mut_arg(id, |arg| arg.help(help).long_help(long_help));
+        }
+    }
+    let names: Vec<String> = command.get_subcommands().map(|sub| sub.get_name().to_string()).collect();
+    for name in names {
+        let prefix = format!("{}.{}", prefix, name);
+        command = command.mut_subcommand(name, |sub| localize(sub, &prefix));
+    }
+    command
+}
+
+fn history_store(dir: Option<PathBuf>, config: &Config) -> Result<SnapshotStore> {
+    let dir = dir
+        .or_else(|| config.history_dir())
+        .with_context(|| t("cli.need_history"))?;
+    Ok(SnapshotStore::new(&dir))
+}
+
+fn main() -> Result<ExitCode> {
     // Configurar logging
     tracing_subscriber::registry()
-        .with(fmt::layer())
+        .with(fmt::layer().with_writer(std::io::stderr))
         .with(EnvFilter::from_default_env().add_directive(tracing::Level::INFO.into()))
         .init();
 
-    let cli = Cli::parse();
+    // O idioma precisa ser conhecido antes do parse, para traduzir a ajuda
+    i18n::set_lang(requested_lang());
+    let matches = localize(Cli::command(), "help").get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let mut config = Config::load(cli.config.as_deref())?;
 
     match cli.command {
-        Commands::Zabbix { file, summary } => {
-            info!("Analisando arquivo Zabbix: {:?}", file);
+        Commands::Zabbix { file, filter, summary, format, template } => {
+            info!("{}", tf("log.zabbix_file", &[("file", &file.display())]));
             
             let data = DataLoader::load_file(&file)?;
             let hosts = ZabbixParser::parse(&data)?;
-            let estacoes = ZabbixParser::to_estacao_info(hosts)?;
+            let estacoes = filter.filter().apply(ZabbixParser::to_estacao_info(hosts, &config)?);
             
-            println!("\n{}", "=== ANÁLISE ZABBIX ===".green().bold());
-            println!("Total de estações: {}", estacoes.len());
+            let report = stations_report(DataSource::Zabbix, &estacoes, &[], &[], format, template.as_deref())?;
+            if let Some(report) = report {
+                println!("{}", report);
+                return Ok(ExitCode::SUCCESS);
+            }
+            
+            println!("\n{}", t("cli.zabbix_title").green().bold());
+            println!("{}", tf("cli.total", &[("count", &i18n::number(estacoes.len()))]));
             
             if summary {
                 let aggregated = Aggregator::group_by_status_and_regional(&estacoes);
@@ -87,15 +602,37 @@ fn main() -> Result<()> {
             }
         }
         
-        Commands::SharePoint { file, summary } => {
-            info!("Analisando arquivo SharePoint: {:?}", file);
+        Commands::SharePoint { file, source, filter, summary, format, template } => {
+            info!("{}", t("log.sharepoint"));
             
-            let data = DataLoader::load_file(&file)?;
-            let records = CsvParser::parse(&data)?;
-            let estacoes = CsvParser::to_estacao_info(records)?;
+            let (estacoes, diagnostics) = source.load_estacoes(file.as_deref(), &config)?;
+            let filter = filter.filter();
+            let estacoes = filter.apply(estacoes);
+            let (diagnostics, unattributed) = filter.diagnostics(diagnostics, &estacoes, &config);
             
-            println!("\n{}", "=== ANÁLISE SHAREPOINT ===".blue().bold());
-            println!("Total de estações: {}", estacoes.len());
+            let report = stations_report(
+                DataSource::SharePoint,
+                &estacoes,
+                &diagnostics,
+                &unattributed,
+                format,
+                template.as_deref(),
+            )?;
+            if let Some(report) = report {
+                println!("{}", report);
+                return Ok(ExitCode::SUCCESS);
+            }
+            
+            println!("\n{}", t("cli.sharepoint_title").blue().bold());
+            println!("{}", tf("cli.total", &[("count", &i18n::number(estacoes.len()))]));
+            if !diagnostics.is_empty() {
+                let count = i18n::number(diagnostics.len()).yellow();
+                println!("{}", tf("cli.diagnostics", &[("count", &count)]));
+            }
+            if !unattributed.is_empty() {
+                let count = i18n::number(unattributed.len()).yellow();
+                println!("{}", tf("cli.unattributed_diagnostics", &[("count", &count)]));
+            }
             
             if summary {
                 let aggregated = Aggregator::group_by_status_and_regional(&estacoes);
@@ -104,32 +641,187 @@ fn main() -> Result<()> {
             }
         }
         
-        Commands::Compare { zabbix, sharepoint, output } => {
-            info!("Comparando Zabbix com SharePoint");
+        Commands::Compare {
+            zabbix,
+            sharepoint,
+            sharepoint_api,
+            filter,
+            fields,
+            duplicate_policy,
+            aliases,
+            waivers,
+            save,
+            history_dir,
+            output,
+            export,
+            format,
+            template,
+            fail_on,
+        } => {
+            info!("{}", t("log.compare"));
+            
+            // Arquivos nunca recebem códigos de cor
+            if output.is_some() {
+                colored::control::set_override(false);
+            }
+            
+            if !fields.is_empty() {
+                config.compare.fields = fields;
+            }
+            if let Some(policy) = duplicate_policy {
+                config.compare.duplicate_policy = policy;
+            }
             
             // Carregar dados do Zabbix
-            let zabbix_data = DataLoader::load_file(&zabbix)?;
-            let zabbix_hosts = ZabbixParser::parse(&zabbix_data)?;
-            let zabbix_estacoes = ZabbixParser::to_estacao_info(zabbix_hosts)?;
+            let zabbix_hosts = zabbix.load_hosts(&config)?;
+            let zabbix_estacoes = ZabbixParser::to_estacao_info(zabbix_hosts, &config)?;
             
             // Carregar dados do SharePoint
-            let sp_data = DataLoader::load_file(&sharepoint)?;
-            let sp_records = CsvParser::parse(&sp_data)?;
-            let sp_estacoes = CsvParser::to_estacao_info(sp_records)?;
+            let (sp_estacoes, diagnostics) =
+                sharepoint_api.load_estacoes(sharepoint.as_deref(), &config)?;
+            
+            let aliases = match aliases.or_else(|| config.aliases_path()) {
+                Some(path) => Aliases::load(&path)?,
+                None => Aliases::default(),
+            };
+            
+            let filter = filter.filter();
+            let (zabbix_estacoes, sp_estacoes) =
+                filter.scope(zabbix_estacoes, sp_estacoes, &config, &aliases);
+            let (diagnostics, unattributed) = filter.diagnostics(diagnostics, &sp_estacoes, &config);
+            if save && !filter.is_empty() {
+                warn!("{}", t("log.scoped_snapshot"));
+            }
+            
+            let inputs = save.then(|| (zabbix_estacoes.clone(), sp_estacoes.clone()));
+            // Registros do SharePoint para a matriz do painel HTML
+            let html_estacoes =
+                (template.is_none() && format == OutputFormat::Html).then(|| sp_estacoes.clone());
             
             // Comparar
-            let comparison = Comparator::compare(zabbix_estacoes, sp_estacoes);
-            let report = DiscrepancyReport::generate(&comparison);
+            let mut comparison =
+                Comparator::compare_with_aliases(zabbix_estacoes, sp_estacoes, &config, &aliases);
+            comparison.diagnostics = diagnostics;
+            comparison.unattributed_diagnostics = unattributed;
+            
+            if let Some(path) = waivers.or_else(|| config.waivers_path()) {
+                let today = chrono::Local::now().date_naive();
+                Waivers::load(&path)?.apply(&mut comparison, today, &config);
+            }
+            let report = match (&template, format) {
+                (Some(path), _) => TemplateRenderer::from_file(path)?
+                    .with_data(&comparison)?
+                    .render(&DiscrepancyReport::model(&comparison))?,
+                (None, OutputFormat::Text) => DiscrepancyReport::generate(&comparison),
+                (None, OutputFormat::Json) => JsonReport::compare(&comparison)?,
+                (None, OutputFormat::Html) => {
+                    let stations = Aggregator::group_by_status_and_regional(&html_estacoes.unwrap_or_default());
+                    HtmlReport::comparison(&comparison, &stations, DataSource::SharePoint)
+                }
+                (None, OutputFormat::Markdown) => {
+                    MarkdownRenderer.render(&DiscrepancyReport::model(&comparison))?
+                }
+            };
+            
+            if let Some(path) = export {
+                TableReport::export(&comparison, &path)?;
+                info!("{}", tf("log.exported", &[("path", &path.display())]));
+            }
+            
+            let check = (!fail_on.is_empty()).then(|| ThresholdCheck::evaluate(&comparison, &fail_on));
+            
+            if let Some((zabbix_inputs, sharepoint_inputs)) = inputs {
+                let store = history_store(history_dir, &config)?;
+                let path = store.save(&Snapshot::new(zabbix_inputs, sharepoint_inputs, comparison))?;
+                info!("{}", tf("log.snapshot_saved", &[("path", &path.display())]));
+            }
             
             // Salvar ou imprimir relatório
             if let Some(output_path) = output {
                 DataLoader::save_file(&output_path, &report)?;
-                println!("{}", format!("Relatório salvo em: {:?}", output_path).green());
+                println!("{}", tf("cli.report_saved", &[("path", &output_path.display())]).green());
             } else {
                 println!("{}", report);
             }
+            
+            // Resumo para pipelines e código de saída do primeiro limite violado
+            if let Some(check) = check {
+                eprintln!("{}", check.summary_line()?);
+                return Ok(ExitCode::from(check.exit_code));
+            }
+        }
+        
+        Commands::Link { zabbix, sharepoint, note, aliases } => {
+            let path = aliases
+                .or_else(|| config.aliases_path())
+                .with_context(|| t("cli.need_aliases"))?;
+            
+            Aliases::append(&path, &Alias { zabbix, sharepoint, note })?;
+            println!("{}", tf("cli.alias_saved", &[("path", &path.display())]).green());
+        }
+        
+        Commands::History { command, history_dir } => {
+            let store = history_store(history_dir, &config)?;
+            
+            match command {
+                HistoryCommand::List => {
+                    for id in store.list()? {
+                        println!("{}", id);
+                    }
+                }
+                HistoryCommand::Diff { from, to, format, template } => {
+                    let diff = store.diff(from.as_deref(), to.as_deref())?;
+                    println!(
+                        "{}",
+                        model_report(
+                            &HistoryReport::model(&diff),
+                            &diff,
+                            || Ok(JsonReport::history_diff(&diff)?),
+                            format,
+                            template.as_deref(),
+                        )?
+                    );
+                }
+            }
+        }
+        
+        Commands::Trend { source, period, filter, csv, history_dir, format, template } => {
+            let store = history_store(history_dir, &config)?;
+            let trend = Trend::from_store(&store, source, period, &filter.filter())?;
+            println!(
+                "{}",
+                model_report(
+                    &TrendReport::model(&trend),
+                    &trend,
+                    || Ok(JsonReport::trend(&trend)?),
+                    format,
+                    template.as_deref(),
+                )?
+            );
+            
+            if let Some(path) = csv {
+                DataLoader::save_file(&path, &trend.to_csv()?)?;
+                println!("{}", tf("cli.series_saved", &[("path", &path.display())]).green());
+            }
         }
     }
 
-    Ok(())
-}
\ No newline at end of file
+    Ok(ExitCode::SUCCESS)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fail_on_merges_commas_and_repeats() {
+        let cli = Cli::try_parse_from([
+            "estacoes-manager", "compare", "--fail-on", "status>2,any", "--fail-on", "only-in-zabbix",
+        ])
+        .unwrap();
+        let Commands::Compare { fail_on, .. } = cli.command else { panic!("esperado compare") };
+        let rules: Vec<String> = fail_on.iter().map(Threshold::to_string).collect();
+        assert_eq!(rules, ["status>2", "any", "only_in_zabbix"]);
+
+        assert!(Cli::try_parse_from(["estacoes-manager", "compare", "--fail-on", "status,nada"]).is_err());
+    }
+}
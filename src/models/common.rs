@@ -1,3 +1,4 @@
+use crate::utils::choice::Choice;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -31,6 +32,7 @@ impl fmt::Display for Status {
 }
 
 impl Status {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "ativo" => Some(Status::Ativo),
@@ -59,6 +61,20 @@ impl Regional {
     pub fn new(s: String) -> Self {
         Regional(s)
     }
+    
+    /// Código da regional sem descrição (ex.: "GR01 - São Paulo" → "GR01"),
+    /// usado para comparar regionais entre as origens
+    pub fn code(&self) -> String {
+        self.0
+            .split(|c: char| c.is_whitespace() || c == '-' || c == '/' || c == ',')
+            .find(|part| !part.is_empty())
+            .unwrap_or("")
+            .to_uppercase()
+    }
+    
+    pub fn same_as(&self, other: &Regional) -> bool {
+        self.code() == other.code()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +85,27 @@ pub struct EstacaoInfo {
     pub regional: Option<Regional>,
     pub tipo: TipoEstacao,
     pub source: DataSource,
+    /// Endereço IP da estação (interface no Zabbix, "IP OpenVPN" no SharePoint)
+    #[serde(default)]
+    pub ip: Option<String>,
+    /// hostid do Zabbix (próprio ou apontado pelo "Link Zabbix")
+    #[serde(default)]
+    pub zabbix_hostid: Option<String>,
+    /// "Situação Litígio" do SharePoint
+    #[serde(default)]
+    pub litigio: Option<String>,
+    /// "Detentor" do SharePoint
+    #[serde(default)]
+    pub detentor: Option<String>,
+    /// "Responsável" do SharePoint
+    #[serde(default)]
+    pub responsavel: Option<String>,
+    /// "Pendência" do SharePoint
+    #[serde(default)]
+    pub pendencia: Option<String>,
+    /// "Local:UF" do SharePoint
+    #[serde(default)]
+    pub uf: Option<String>,
 }
 
 impl EstacaoInfo {
@@ -87,10 +124,30 @@ impl EstacaoInfo {
             regional,
             tipo,
             source,
+            ip: None,
+            zabbix_hostid: None,
+            litigio: None,
+            detentor: None,
+            responsavel: None,
+            pendencia: None,
+            uf: None,
         }
     }
 }
 
+/// Estação RFeye para os testes, com o ID repetido no nome
+#[cfg(test)]
+pub(crate) fn estacao(id: &str, status: Status, regional: Option<&str>, source: DataSource) -> EstacaoInfo {
+    EstacaoInfo::new(
+        id.to_string(),
+        id.to_string(),
+        status,
+        regional.map(|r| Regional::new(r.to_string())),
+        TipoEstacao::RFeye,
+        source,
+    )
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TipoEstacao {
     RFeye,
@@ -115,6 +172,7 @@ impl fmt::Display for TipoEstacao {
 }
 
 impl TipoEstacao {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s {
             "RFeye" => TipoEstacao::RFeye,
@@ -127,12 +185,23 @@ impl TipoEstacao {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DataSource {
     Zabbix,
     SharePoint,
 }
 
+impl Choice for DataSource {
+    const ALL: &'static [Self] = &[DataSource::Zabbix, DataSource::SharePoint];
+
+    fn name(self) -> &'static str {
+        match self {
+            DataSource::Zabbix => "zabbix",
+            DataSource::SharePoint => "share-point",
+        }
+    }
+}
+
 impl fmt::Display for DataSource {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
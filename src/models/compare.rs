@@ -0,0 +1,155 @@
+//! Regras da comparação entre Zabbix e SharePoint, compartilhadas pela
+//! configuração e pelo comparador.
+
+use crate::i18n::t;
+use crate::models::common::{EstacaoInfo, Status, TipoEstacao};
+use crate::utils::choice::Choice;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Registro que participa da comparação quando vários colidem na mesma
+/// chave
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicatePolicy {
+    /// O primeiro na ordem de leitura
+    First,
+    /// O último na ordem de leitura
+    #[default]
+    Last,
+    /// Nenhum: os registros duplicados ficam fora da comparação
+    Skip,
+}
+
+impl Choice for DuplicatePolicy {
+    const ALL: &'static [Self] = &[DuplicatePolicy::First, DuplicatePolicy::Last, DuplicatePolicy::Skip];
+
+    fn name(self) -> &'static str {
+        match self {
+            DuplicatePolicy::First => "first",
+            DuplicatePolicy::Last => "last",
+            DuplicatePolicy::Skip => "skip",
+        }
+    }
+}
+
+/// Par de estados considerado consistente mesmo com status diferentes.
+/// Campos omitidos aceitam qualquer valor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusRule {
+    /// Status no SharePoint
+    #[serde(default)]
+    pub sharepoint: Option<Status>,
+    /// Exige "Situação Litígio" preenchida (`true`) ou vazia (`false`)
+    #[serde(default)]
+    pub litigio: Option<bool>,
+    /// Status no Zabbix
+    #[serde(default)]
+    pub zabbix: Option<Status>,
+    /// A regra vale para hosts ausentes do Zabbix
+    #[serde(default)]
+    pub zabbix_absent: bool,
+}
+
+impl StatusRule {
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self {
+                sharepoint: Some(Status::Baixa),
+                litigio: None,
+                zabbix: None,
+                zabbix_absent: true,
+            },
+            Self {
+                sharepoint: Some(Status::Manutencao),
+                litigio: None,
+                zabbix: Some(Status::Defeito),
+                zabbix_absent: false,
+            },
+            Self {
+                sharepoint: None,
+                litigio: Some(true),
+                zabbix: Some(Status::Litigio),
+                zabbix_absent: false,
+            },
+        ]
+    }
+
+    /// Verifica a regra para um registro do SharePoint e o host
+    /// correspondente (`None` quando ausente do Zabbix)
+    pub fn matches(&self, zabbix: Option<&EstacaoInfo>, sharepoint: &EstacaoInfo) -> bool {
+        let sharepoint_ok = self.sharepoint.as_ref().is_none_or(|s| *s == sharepoint.status)
+            && self.litigio.is_none_or(|l| l == sharepoint.litigio.is_some());
+        let zabbix_ok = match zabbix {
+            None => self.zabbix_absent,
+            Some(host) => {
+                !self.zabbix_absent && self.zabbix.as_ref().is_none_or(|s| *s == host.status)
+            }
+        };
+        sharepoint_ok && zabbix_ok
+    }
+}
+
+/// Atributos que podem ser comparados entre Zabbix e SharePoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComparableField {
+    Status,
+    Regional,
+    Tipo,
+    Ip,
+    HostLink,
+}
+
+impl Choice for ComparableField {
+    const ALL: &'static [Self] = &[
+        ComparableField::Status,
+        ComparableField::Regional,
+        ComparableField::Tipo,
+        ComparableField::Ip,
+        ComparableField::HostLink,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            ComparableField::Status => "status",
+            ComparableField::Regional => "regional",
+            ComparableField::Tipo => "tipo",
+            ComparableField::Ip => "ip",
+            ComparableField::HostLink => "host-link",
+        }
+    }
+}
+
+impl ComparableField {
+    /// Valor exibido do campo, ou `None` quando a origem não o informa.
+    /// Status `Desconhecido` (sem grupo de status no Zabbix, ou valor
+    /// ilegível já apontado no diagnóstico de leitura) não é comparável.
+    pub fn value(&self, estacao: &EstacaoInfo) -> Option<String> {
+        match self {
+            ComparableField::Status => {
+                (estacao.status != Status::Desconhecido).then(|| estacao.status.to_string())
+            }
+            ComparableField::Regional => estacao.regional.as_ref().map(|r| r.to_string()),
+            ComparableField::Tipo => match &estacao.tipo {
+                TipoEstacao::Outro(_) => None,
+                tipo => Some(tipo.to_string()),
+            },
+            ComparableField::Ip => estacao.ip.clone(),
+            ComparableField::HostLink => estacao.zabbix_hostid.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ComparableField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self {
+            ComparableField::Status => "field.status",
+            ComparableField::Regional => "field.regional",
+            ComparableField::Tipo => "field.tipo",
+            ComparableField::Ip => "field.ip",
+            ComparableField::HostLink => "field.host_link",
+        };
+        f.write_str(&t(key))
+    }
+}
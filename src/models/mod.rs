@@ -1,7 +1,9 @@
 pub mod common;
+pub mod compare;
 pub mod sharepoint;
 pub mod zabbix;
 
 pub use common::{DataSource, EstacaoInfo, Regional, Status, TipoEstacao};
-pub use sharepoint::SharePointRecord;
-pub use zabbix::{ZabbixGroup, ZabbixHost, ZabbixResponse};
\ No newline at end of file
+pub use compare::{ComparableField, DuplicatePolicy, StatusRule};
+pub use sharepoint::{SharePointApi, SharePointField, SharePointRecord};
+pub use zabbix::{RawZabbixHost, ZabbixGroup, ZabbixHost, ZabbixResponse, ZabbixVersion};
\ No newline at end of file
@@ -1,58 +1,179 @@
-use serde::Deserialize;
+use crate::utils::choice::Choice;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SharePointRecord {
-    #[serde(rename = "Local:UF")]
+    /// Linha no arquivo (CSV) ou posição do item (API)
+    pub linha: Option<u64>,
     pub uf: Option<String>,
-    
-    #[serde(rename = "Local:Município")]
     pub municipio: Option<String>,
-    
-    #[serde(rename = "Local")]
     pub local: Option<String>,
-    
-    #[serde(rename = "Detentor")]
     pub detentor: Option<String>,
-    
-    #[serde(rename = "Tipo de Estação")]
     pub tipo_estacao: Option<String>,
-    
-    #[serde(rename = "ID de rede")]
     pub id_rede: String,
-    
-    #[serde(rename = "Situação do Equipamento")]
     pub situacao: String,
-    
-    #[serde(rename = "Situação Litígio")]
     pub situacao_litigio: Option<String>,
-    
-    #[serde(rename = "Instrumento Fiscaliza")]
     pub instrumento_fiscaliza: Option<String>,
-    
-    #[serde(rename = "Link Zabbix")]
     pub link_zabbix: Option<String>,
-    
-    #[serde(rename = "IP OpenVPN")]
     pub ip_openvpn: Option<String>,
-    
-    #[serde(rename = "Patrimônio")]
     pub patrimonio: Option<String>,
-    
-    #[serde(rename = "Observações")]
     pub observacoes: Option<String>,
-    
-    #[serde(rename = "Pendência")]
     pub pendencia: Option<String>,
-    
-    #[serde(rename = "Ações a serem adotadas")]
     pub acoes: Option<String>,
-    
-    #[serde(rename = "Responsável na Anatel pela ação")]
     pub responsavel: Option<String>,
-    
-    #[serde(rename = "Modificado")]
     pub modificado: Option<String>,
-    
-    #[serde(rename = "Modificado por")]
     pub modificado_por: Option<String>,
-}
\ No newline at end of file
+}
+
+/// API usada para ler os itens da lista
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SharePointApi {
+    /// SharePoint REST (`/_api/web/lists/getbytitle(...)/items`)
+    Rest,
+    /// Microsoft Graph (`/sites/{site}/lists/{list}/items`)
+    Graph,
+}
+
+impl Choice for SharePointApi {
+    const ALL: &'static [Self] = &[SharePointApi::Rest, SharePointApi::Graph];
+
+    fn name(self) -> &'static str {
+        match self {
+            SharePointApi::Rest => "rest",
+            SharePointApi::Graph => "graph",
+        }
+    }
+}
+
+/// Campos lógicos de um registro, independentes do título da coluna
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SharePointField {
+    Uf,
+    Municipio,
+    Local,
+    Detentor,
+    Tipo,
+    Id,
+    Status,
+    Litigio,
+    InstrumentoFiscaliza,
+    LinkZabbix,
+    Ip,
+    Patrimonio,
+    Observacoes,
+    Pendencia,
+    Acoes,
+    Responsavel,
+    Modificado,
+    ModificadoPor,
+}
+
+impl SharePointField {
+    pub const ALL: &'static [SharePointField] = &[
+        SharePointField::Uf,
+        SharePointField::Municipio,
+        SharePointField::Local,
+        SharePointField::Detentor,
+        SharePointField::Tipo,
+        SharePointField::Id,
+        SharePointField::Status,
+        SharePointField::Litigio,
+        SharePointField::InstrumentoFiscaliza,
+        SharePointField::LinkZabbix,
+        SharePointField::Ip,
+        SharePointField::Patrimonio,
+        SharePointField::Observacoes,
+        SharePointField::Pendencia,
+        SharePointField::Acoes,
+        SharePointField::Responsavel,
+        SharePointField::Modificado,
+        SharePointField::ModificadoPor,
+    ];
+
+    /// Título padrão da coluna na lista
+    pub fn title(&self) -> &'static str {
+        match self {
+            SharePointField::Uf => "Local:UF",
+            SharePointField::Municipio => "Local:Município",
+            SharePointField::Local => "Local",
+            SharePointField::Detentor => "Detentor",
+            SharePointField::Tipo => "Tipo de Estação",
+            SharePointField::Id => "ID de rede",
+            SharePointField::Status => "Situação do Equipamento",
+            SharePointField::Litigio => "Situação Litígio",
+            SharePointField::InstrumentoFiscaliza => "Instrumento Fiscaliza",
+            SharePointField::LinkZabbix => "Link Zabbix",
+            SharePointField::Ip => "IP OpenVPN",
+            SharePointField::Patrimonio => "Patrimônio",
+            SharePointField::Observacoes => "Observações",
+            SharePointField::Pendencia => "Pendência",
+            SharePointField::Acoes => "Ações a serem adotadas",
+            SharePointField::Responsavel => "Responsável na Anatel pela ação",
+            SharePointField::Modificado => "Modificado",
+            SharePointField::ModificadoPor => "Modificado por",
+        }
+    }
+
+    /// Nome interno das colunas nativas da lista, que não deriva do título
+    pub fn builtin_name(&self) -> Option<&'static str> {
+        match self {
+            SharePointField::Modificado => Some("Modified"),
+            SharePointField::ModificadoPor => Some("Editor"),
+            _ => None,
+        }
+    }
+
+    pub fn is_required(&self) -> bool {
+        matches!(self, SharePointField::Id | SharePointField::Status)
+    }
+}
+
+impl fmt::Display for SharePointField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.title())
+    }
+}
+
+impl SharePointRecord {
+    /// hostid do host apontado pelo "Link Zabbix"
+    /// (`...hosts.php?form=update&hostid=10084`, `...?hostids[]=10084`)
+    pub fn linked_hostid(&self) -> Option<String> {
+        let link = self.link_zabbix.as_deref()?;
+        ["hostid=", "hostids[]=", "hostids%5B%5D="].iter().find_map(|key| {
+            let start = link.find(key)? + key.len();
+            let digits: String = link[start..]
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect();
+            (!digits.is_empty()).then_some(digits)
+        })
+    }
+
+    /// Atribui o valor de um campo; textos vazios contam como ausentes
+    pub fn set(&mut self, field: SharePointField, value: Option<String>) {
+        let value = value.filter(|v| !v.trim().is_empty());
+        match field {
+            SharePointField::Uf => self.uf = value,
+            SharePointField::Municipio => self.municipio = value,
+            SharePointField::Local => self.local = value,
+            SharePointField::Detentor => self.detentor = value,
+            SharePointField::Tipo => self.tipo_estacao = value,
+            SharePointField::Id => self.id_rede = value.unwrap_or_default(),
+            SharePointField::Status => self.situacao = value.unwrap_or_default(),
+            SharePointField::Litigio => self.situacao_litigio = value,
+            SharePointField::InstrumentoFiscaliza => self.instrumento_fiscaliza = value,
+            SharePointField::LinkZabbix => self.link_zabbix = value,
+            SharePointField::Ip => self.ip_openvpn = value,
+            SharePointField::Patrimonio => self.patrimonio = value,
+            SharePointField::Observacoes => self.observacoes = value,
+            SharePointField::Pendencia => self.pendencia = value,
+            SharePointField::Acoes => self.acoes = value,
+            SharePointField::Responsavel => self.responsavel = value,
+            SharePointField::Modificado => self.modificado = value,
+            SharePointField::ModificadoPor => self.modificado_por = value,
+        }
+    }
+}
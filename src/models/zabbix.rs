@@ -1,19 +1,120 @@
 use serde::Deserialize;
+use std::fmt;
 
 #[derive(Debug, Deserialize)]
 pub struct ZabbixResponse {
     #[serde(rename = "jsonrpc")]
     pub jsonrpc: Option<String>,
-    pub result: Vec<ZabbixHost>,
+    pub result: Vec<RawZabbixHost>,
     pub id: Option<i32>,
+    /// Metadados opcionais gravados junto da exportação
+    #[serde(default)]
+    pub apiinfo: Option<ZabbixApiInfo>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+pub struct ZabbixApiInfo {
+    pub version: String,
+}
+
+/// Versão da API do Zabbix (`apiinfo.version`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZabbixVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ZabbixVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut parts = raw.trim().split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().unwrap_or("0").parse().ok()?;
+        // Ignora sufixos como "0rc1"
+        let patch = parts
+            .next()
+            .map(|p| p.chars().take_while(char::is_ascii_digit).collect::<String>())
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(0);
+        Some(Self::new(major, minor, patch))
+    }
+
+    /// A partir da 6.2 os grupos vêm em `hostgroups` (`selectHostGroups`)
+    pub fn uses_hostgroups(&self) -> bool {
+        *self >= Self::new(6, 2, 0)
+    }
+
+    /// A partir da 5.4 `user.login` recebe `username` em vez de `user`
+    pub fn uses_username(&self) -> bool {
+        *self >= Self::new(5, 4, 0)
+    }
+
+    /// A partir da 6.4 a autenticação vai no cabeçalho `Authorization`
+    pub fn supports_bearer(&self) -> bool {
+        *self >= Self::new(6, 4, 0)
+    }
+}
+
+impl fmt::Display for ZabbixVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Host como retornado por `host.get`, em qualquer versão: até a 6.0 os
+/// grupos vêm em `groups`, a partir da 6.2 em `hostgroups`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawZabbixHost {
+    pub hostid: String,
+    pub host: String,
+    pub name: String,
+    #[serde(default)]
+    pub groups: Option<Vec<ZabbixGroup>>,
+    #[serde(default)]
+    pub hostgroups: Option<Vec<ZabbixGroup>>,
+    #[serde(default)]
+    pub interfaces: Vec<ZabbixInterface>,
+}
+
+impl RawZabbixHost {
+    /// Converte para a representação normalizada, priorizando o campo de
+    /// grupos próprio da versão informada
+    pub fn into_host(self, version: Option<&ZabbixVersion>) -> ZabbixHost {
+        let prefer_hostgroups = version.map(ZabbixVersion::uses_hostgroups).unwrap_or(true);
+        let (preferred, fallback) = if prefer_hostgroups {
+            (self.hostgroups, self.groups)
+        } else {
+            (self.groups, self.hostgroups)
+        };
+
+        ZabbixHost {
+            hostid: self.hostid,
+            host: self.host,
+            name: self.name,
+            groups: preferred.or(fallback).unwrap_or_default(),
+            interfaces: self.interfaces,
+        }
+    }
+}
+
+impl From<RawZabbixHost> for ZabbixHost {
+    fn from(raw: RawZabbixHost) -> Self {
+        raw.into_host(None)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "RawZabbixHost")]
 pub struct ZabbixHost {
     pub hostid: String,
     pub host: String,
     pub name: String,
     pub groups: Vec<ZabbixGroup>,
+    pub interfaces: Vec<ZabbixInterface>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -28,15 +129,46 @@ pub struct ZabbixGroup {
     pub uuid: Option<String>,
 }
 
+/// Interface do host (`selectInterfaces`). A API devolve todos os campos
+/// como texto.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ZabbixInterface {
+    pub ip: String,
+    pub dns: String,
+    pub port: String,
+    /// 1 = agente, 2 = SNMP, 3 = IPMI, 4 = JMX
+    #[serde(rename = "type")]
+    pub interface_type: String,
+    /// "1" na interface padrão de cada tipo
+    pub main: String,
+}
+
+impl ZabbixInterface {
+    pub fn is_main(&self) -> bool {
+        self.main == "1"
+    }
+}
+
 impl ZabbixHost {
+    /// IP da interface padrão, preferindo a do agente
+    pub fn main_ip(&self) -> Option<&str> {
+        let with_ip = || self.interfaces.iter().filter(|i| !i.ip.trim().is_empty());
+        with_ip()
+            .filter(|i| i.is_main())
+            .min_by_key(|i| i.interface_type != "1")
+            .or_else(|| with_ip().next())
+            .map(|i| i.ip.trim())
+    }
+    
     pub fn get_group_by_id(&self, id: &str) -> Option<&ZabbixGroup> {
         self.groups.iter().find(|g| g.groupid == id)
     }
-    
+
     pub fn get_groups_by_name_prefix(&self, prefix: &str) -> Vec<&ZabbixGroup> {
         self.groups
             .iter()
             .filter(|g| g.name.starts_with(prefix))
             .collect()
     }
-}
\ No newline at end of file
+}
@@ -1,38 +1,295 @@
 use crate::{
+    config::Config,
     error::{AppError, Result},
     models::{
         common::{DataSource, EstacaoInfo, Regional, Status, TipoEstacao},
-        sharepoint::SharePointRecord,
+        sharepoint::{SharePointField, SharePointRecord},
     },
-    
+    parsers::diagnostic::{DiagnosticReason, InvalidRowPolicy, ParseDiagnostic, ParseMode},
+    utils::normalizer::Normalizer,
 };
+use std::collections::HashMap;
+use tracing::debug;
 
 pub struct CsvParser;
 
+/// Tabela cabeçalho → campo lógico usada para ler a lista do SharePoint.
+///
+/// Os cabeçalhos são comparados sem diferenciar maiúsculas, acentos e
+/// espaços, e os nomes internos (`ID_x0020_de_x0020_rede`) são
+/// decodificados antes da comparação.
+#[derive(Debug, Clone)]
+pub struct ColumnMapping {
+    headers: HashMap<String, SharePointField>,
+    /// Cabeçalhos de `[sharepoint.columns]`, na ordem do arquivo
+    configured: Vec<String>,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        let mut mapping = Self {
+            headers: HashMap::new(),
+            configured: Vec::new(),
+        };
+        for field in SharePointField::ALL {
+            mapping.add(field.title(), *field);
+            mapping.add(&Self::internal_name(field.title()), *field);
+            if let Some(builtin) = field.builtin_name() {
+                mapping.add(builtin, *field);
+            }
+        }
+        mapping
+    }
+}
+
+impl ColumnMapping {
+    /// Mapeamento padrão acrescido dos cabeçalhos de `[sharepoint.columns]`
+    pub fn from_config(config: &Config) -> Self {
+        let mut mapping = Self::default();
+        for (header, field) in &config.sharepoint.columns {
+            mapping.add(header, *field);
+            mapping.configured.push(header.clone());
+        }
+        mapping
+    }
+    
+    /// Nomes internos a pedir à API: os derivados dos títulos padrão
+    /// (que não mudam quando a coluna é renomeada) e os dos cabeçalhos
+    /// configurados
+    pub fn select_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = SharePointField::ALL
+            .iter()
+            .filter(|field| field.builtin_name().is_none())
+            .map(|field| Self::internal_name(field.title()))
+            .collect();
+        for header in &self.configured {
+            let name = Self::internal_name(header);
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+    
+    pub fn add(&mut self, header: &str, field: SharePointField) {
+        self.headers.insert(Self::normalize_header(header), field);
+    }
+    
+    pub fn field_for(&self, header: &str) -> Option<SharePointField> {
+        self.headers.get(&Self::normalize_header(header)).copied()
+    }
+    
+    /// Associa cada posição do cabeçalho a um campo, exigindo os campos
+    /// obrigatórios
+    pub fn resolve(&self, headers: &[&str]) -> Result<Vec<Option<SharePointField>>> {
+        let resolved: Vec<Option<SharePointField>> = headers
+            .iter()
+            .map(|header| {
+                let field = self.field_for(header);
+                if field.is_none() {
+                    debug!("Coluna ignorada: {}", header);
+                }
+                field
+            })
+            .collect();
+        
+        let missing = SharePointField::ALL
+            .iter()
+            .filter(|field| field.is_required() && !resolved.contains(&Some(**field)));
+        Self::check_missing(missing)?;
+        
+        Ok(resolved)
+    }
+    
+    pub fn check_missing<'a>(missing: impl Iterator<Item = &'a SharePointField>) -> Result<()> {
+        let missing: Vec<String> = missing
+            .map(|field| format!("\"{}\"", field.title()))
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::MissingColumns(missing))
+        }
+    }
+    
+    fn normalize_header(header: &str) -> String {
+        let decoded = Self::decode_internal_name(header.trim_start_matches('\u{feff}'));
+        Normalizer::normalize_text(&decoded)
+            .to_lowercase()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+    
+    /// Nome interno gerado pelo SharePoint a partir do título da coluna:
+    /// caracteres fora de `[A-Za-z0-9_]` viram `_xHHHH_` e o resultado é
+    /// truncado em 32 caracteres.
+    pub fn internal_name(title: &str) -> String {
+        let mut name = String::new();
+        for c in title.chars() {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                name.push(c);
+            } else {
+                name.push_str(&format!("_x{:04x}_", c as u32));
+            }
+        }
+        name.chars().take(32).collect()
+    }
+    
+    /// Decodifica as sequências `_xHHHH_` de um nome interno
+    pub fn decode_internal_name(name: &str) -> String {
+        let mut decoded = String::new();
+        let mut rest = name;
+        
+        while let Some(pos) = rest.find("_x") {
+            decoded.push_str(&rest[..pos]);
+            let candidate = &rest[pos..];
+            let code = candidate
+                .get(2..6)
+                .filter(|_| candidate.get(6..7) == Some("_"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .and_then(char::from_u32);
+            
+            match code {
+                Some(c) => {
+                    decoded.push(c);
+                    rest = &candidate[7..];
+                }
+                None => {
+                    decoded.push_str("_x");
+                    rest = &candidate[2..];
+                }
+            }
+        }
+        decoded.push_str(rest);
+        decoded
+    }
+}
+
 impl CsvParser {
-    pub fn parse(csv_data: &str) -> Result<Vec<SharePointRecord>> {
-        let mut reader = csv::Reader::from_reader(csv_data.as_bytes());
+    pub fn parse(csv_data: &str, config: &Config) -> Result<Vec<SharePointRecord>> {
+        Ok(Self::parse_with(csv_data, config, ParseMode::Strict)?.0)
+    }
+    
+    /// No modo tolerante, linhas que o leitor CSV rejeita são descartadas
+    /// e registradas como diagnóstico
+    pub fn parse_with(
+        csv_data: &str,
+        config: &Config,
+        mode: ParseMode,
+    ) -> Result<(Vec<SharePointRecord>, Vec<ParseDiagnostic>)> {
+        let lenient = matches!(mode, ParseMode::Lenient(_));
+        let mapping = ColumnMapping::from_config(config);
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(lenient)
+            .from_reader(csv_data.as_bytes());
+        let headers = reader.headers()?.clone();
+        let columns = mapping.resolve(&headers.iter().collect::<Vec<_>>())?;
         let mut records = Vec::new();
+        let mut diagnostics = Vec::new();
         
-        for result in reader.deserialize() {
-            let record: SharePointRecord = result?;
+        for result in reader.records() {
+            let row = match result {
+                Ok(row) => row,
+                Err(e) if lenient => {
+                    let line = e.position().map(|p| p.line()).unwrap_or(0);
+                    diagnostics.push(ParseDiagnostic::new(line, "", "", DiagnosticReason::Malformed(e.to_string())));
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+            let line = row.position().map(|p| p.line());
+            
+            if row.len() != headers.len() {
+                diagnostics.push(ParseDiagnostic::new(
+                    line.unwrap_or(0),
+                    "",
+                    "",
+                    DiagnosticReason::ColumnCount {
+                        found: row.len(),
+                        expected: headers.len(),
+                    },
+                ));
+            }
+            
+            let mut record = SharePointRecord {
+                linha: line,
+                ..Default::default()
+            };
+            for (field, value) in columns.iter().zip(row.iter()) {
+                if let Some(field) = field {
+                    record.set(*field, Some(value.to_string()));
+                }
+            }
             records.push(record);
         }
         
-        Ok(records)
+        Ok((records, diagnostics))
     }
     
-    pub fn to_estacao_info(records: Vec<SharePointRecord>) -> Result<Vec<EstacaoInfo>> {
+    pub fn to_estacao_info(records: Vec<SharePointRecord>, config: &Config) -> Result<Vec<EstacaoInfo>> {
+        Ok(Self::to_estacao_info_with(records, config, ParseMode::Strict)?.0)
+    }
+    
+    /// No modo tolerante, registros com status desconhecido viram
+    /// `Status::Desconhecido` ou são descartados, conforme a política, e
+    /// registros sem ID são sempre descartados
+    pub fn to_estacao_info_with(
+        records: Vec<SharePointRecord>,
+        config: &Config,
+        mode: ParseMode,
+    ) -> Result<(Vec<EstacaoInfo>, Vec<ParseDiagnostic>)> {
         let mut estacoes = Vec::new();
+        let mut diagnostics = Vec::new();
         
         for record in records {
-            let status = Status::from_str(&record.situacao)
-                .ok_or_else(|| AppError::InvalidStatus(record.situacao.clone()))?;
+            let row = record.linha.unwrap_or(0);
             
-            let regional = Self::extract_regional(&record);
-            let tipo = Self::extract_tipo(&record);
+            if let ParseMode::Lenient(_) = mode {
+                if record.id_rede.trim().is_empty() {
+                    diagnostics.push(ParseDiagnostic::new(
+                        row,
+                        SharePointField::Id.title(),
+                        "",
+                        DiagnosticReason::EmptyId,
+                    ));
+                    continue;
+                }
+            }
+            
+            let status = match (config.sharepoint_status(&record.situacao), mode) {
+                (Some(status), _) => status,
+                (None, ParseMode::Strict) => {
+                    return Err(AppError::InvalidStatus(record.situacao.clone()));
+                }
+                (None, ParseMode::Lenient(policy)) => {
+                    let reason = match policy {
+                        InvalidRowPolicy::Unknown => DiagnosticReason::UnknownStatus,
+                        InvalidRowPolicy::Skip => DiagnosticReason::UnknownStatusSkipped,
+                    };
+                    let diagnostic = ParseDiagnostic::new(
+                        row,
+                        SharePointField::Status.title(),
+                        &record.situacao,
+                        reason,
+                    );
+                    match policy {
+                        InvalidRowPolicy::Unknown => {
+                            diagnostics.push(diagnostic.with_station(&record.id_rede));
+                            Status::Desconhecido
+                        }
+                        InvalidRowPolicy::Skip => {
+                            diagnostics.push(diagnostic);
+                            continue;
+                        }
+                    }
+                }
+            };
             
-            let estacao = EstacaoInfo::new(
+            let regional = Self::extract_regional(&record, config);
+            let tipo = Self::extract_tipo(&record, config);
+            
+            let mut estacao = EstacaoInfo::new(
                 record.id_rede.clone(),
                 record.id_rede.clone(), // Usando ID como nome também
                 status,
@@ -40,19 +297,26 @@ impl CsvParser {
                 tipo,
                 DataSource::SharePoint,
             );
+            estacao.ip = record.ip_openvpn.clone();
+            estacao.zabbix_hostid = record.linked_hostid();
+            estacao.litigio = record.situacao_litigio.clone();
+            estacao.detentor = record.detentor.clone();
+            estacao.responsavel = record.responsavel.clone();
+            estacao.pendencia = record.pendencia.clone();
+            estacao.uf = record.uf.clone();
             
             estacoes.push(estacao);
         }
         
-        Ok(estacoes)
+        Ok((estacoes, diagnostics))
     }
     
-    fn extract_regional(record: &SharePointRecord) -> Option<Regional> {
+    fn extract_regional(record: &SharePointRecord, config: &Config) -> Option<Regional> {
         if let Some(detentor) = &record.detentor {
-            // Procurar por padrões GR ou UO no campo detentor
+            // Procurar pelos prefixos de regional no campo detentor
             let parts: Vec<&str> = detentor.split(&[' ', ','][..]).collect();
             for part in parts {
-                if part.starts_with("GR") || part.starts_with("UO") {
+                if config.is_regional(part) {
                     return Some(Regional::new(part.to_string()));
                 }
             }
@@ -60,25 +324,88 @@ impl CsvParser {
         None
     }
     
-    fn extract_tipo(record: &SharePointRecord) -> TipoEstacao {
+    fn extract_tipo(record: &SharePointRecord, config: &Config) -> TipoEstacao {
         if let Some(tipo) = &record.tipo_estacao {
             TipoEstacao::from_str(tipo)
         } else {
             // Tentar inferir pelo ID
-            let id = record.id_rede.to_lowercase();
-            if id.contains("rfeye") {
-                TipoEstacao::RFeye
-            } else if id.contains("miaer") {
-                TipoEstacao::MIAer
-            } else if id.contains("cwsm") {
-                TipoEstacao::CelWirelessRMU
-            } else if id.contains("ums") {
-                TipoEstacao::UMS300
-            } else if id.contains("erm") {
-                TipoEstacao::ERMx
-            } else {
-                TipoEstacao::Outro(record.id_rede.clone())
-            }
+            config
+                .tipo_for_name(&record.id_rede)
+                .unwrap_or_else(|| TipoEstacao::Outro(record.id_rede.clone()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_internal_names() {
+        assert_eq!(ColumnMapping::internal_name("ID de rede"), "ID_x0020_de_x0020_rede");
+        assert_eq!(ColumnMapping::decode_internal_name("Situa_x00e7__x00e3_o_x0020_do"), "Situação do");
+        assert_eq!(ColumnMapping::decode_internal_name("Local_x003a_UF"), "Local:UF");
+    }
+    
+    #[test]
+    fn test_headers_are_matched_loosely() {
+        let mapping = ColumnMapping::default();
+        assert_eq!(mapping.field_for("ID de rede"), Some(SharePointField::Id));
+        assert_eq!(mapping.field_for("id  DE rede"), Some(SharePointField::Id));
+        assert_eq!(mapping.field_for("ID_x0020_de_x0020_rede"), Some(SharePointField::Id));
+        assert_eq!(mapping.field_for("Situacao do equipamento"), Some(SharePointField::Status));
+        assert_eq!(mapping.field_for("Situa_x00e7__x00e3_o_x0020_do_x0"), Some(SharePointField::Status));
+        assert_eq!(mapping.field_for("\u{feff}Local:UF"), Some(SharePointField::Uf));
+        assert_eq!(mapping.field_for("Estação"), None);
+    }
+    
+    #[test]
+    fn test_custom_columns_and_missing_required() {
+        let mut config = Config::default();
+        config
+            .sharepoint
+            .columns
+            .insert("Estação".to_string(), SharePointField::Id);
+        
+        let csv = "Estação,Situação do Equipamento\nRFeye002300,Ativo\n";
+        let records = CsvParser::parse(csv, &config).unwrap();
+        assert_eq!(records[0].id_rede, "RFeye002300");
+        assert_eq!(records[0].situacao, "Ativo");
+        
+        let err = CsvParser::parse(csv, &Config::default()).unwrap_err();
+        match err {
+            AppError::MissingColumns(missing) => assert_eq!(missing, vec!["\"ID de rede\""]),
+            other => panic!("erro inesperado: {:?}", other),
         }
     }
+    
+    #[test]
+    fn test_lenient_mode_collects_diagnostics() {
+        let config = Config::default();
+        let csv = "ID de rede,Situação do Equipamento\n\
+                   RFeye002300,Ativo\n\
+                   RFeye002301,Ativvo\n\
+                   ,Ativo\n";
+        let records = CsvParser::parse(csv, &config).unwrap();
+        
+        assert!(matches!(
+            CsvParser::to_estacao_info(records.clone(), &config),
+            Err(AppError::InvalidStatus(_))
+        ));
+        
+        let mode = ParseMode::Lenient(InvalidRowPolicy::Unknown);
+        let (estacoes, diagnostics) =
+            CsvParser::to_estacao_info_with(records.clone(), &config, mode).unwrap();
+        assert_eq!(estacoes.len(), 2);
+        assert_eq!(estacoes[1].status, Status::Desconhecido);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].row, 3);
+        assert_eq!(diagnostics[0].value, "Ativvo");
+        assert_eq!(diagnostics[0].reason, DiagnosticReason::UnknownStatus);
+        assert_eq!(diagnostics[1].reason, DiagnosticReason::EmptyId);
+        
+        let mode = ParseMode::Lenient(InvalidRowPolicy::Skip);
+        let (estacoes, _) = CsvParser::to_estacao_info_with(records, &config, mode).unwrap();
+        assert_eq!(estacoes.len(), 1);
+    }
 }
\ No newline at end of file
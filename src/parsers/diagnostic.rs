@@ -0,0 +1,107 @@
+use crate::i18n::{t, tf};
+use crate::utils::choice::Choice;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Problema encontrado ao ler uma linha da origem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseDiagnostic {
+    /// Linha no arquivo (CSV) ou posição do item (API), a partir de 1
+    pub row: u64,
+    pub column: String,
+    pub value: String,
+    pub reason: DiagnosticReason,
+    /// ID da estação que a linha gerou; vazio quando a linha foi
+    /// descartada ou não chegou a ser lida
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub station: Option<String>,
+}
+
+/// Motivo do diagnóstico, traduzido só na exibição
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticReason {
+    /// Linha rejeitada pelo leitor CSV (mensagem do leitor)
+    Malformed(String),
+    /// Quantidade de colunas diferente da do cabeçalho
+    ColumnCount { found: usize, expected: usize },
+    /// "ID de rede" vazio; a linha é descartada
+    EmptyId,
+    /// Status fora do mapa; a linha fica com status `Desconhecido`
+    UnknownStatus,
+    /// Status fora do mapa; a linha é descartada
+    UnknownStatusSkipped,
+    /// Motivo já em texto, como nos snapshots gravados antes dos códigos
+    #[serde(untagged)]
+    Text(String),
+}
+
+impl ParseDiagnostic {
+    pub fn new(row: u64, column: &str, value: &str, reason: DiagnosticReason) -> Self {
+        Self {
+            row,
+            column: column.to_string(),
+            value: value.to_string(),
+            reason,
+            station: None,
+        }
+    }
+
+    pub fn with_station(mut self, id: &str) -> Self {
+        self.station = Some(id.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&tf(
+            "diagnostic.line",
+            &[("row", &self.row), ("column", &self.column), ("value", &self.value), ("reason", &self.reason)],
+        ))
+    }
+}
+
+impl fmt::Display for DiagnosticReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticReason::Malformed(detail) | DiagnosticReason::Text(detail) => f.write_str(detail),
+            DiagnosticReason::ColumnCount { found, expected } => f.write_str(&tf(
+                "diagnostic.column_count",
+                &[("found", found), ("expected", expected)],
+            )),
+            DiagnosticReason::EmptyId => f.write_str(&t("diagnostic.empty_id")),
+            DiagnosticReason::UnknownStatus => f.write_str(&t("diagnostic.unknown_status")),
+            DiagnosticReason::UnknownStatusSkipped => f.write_str(&t("diagnostic.unknown_status_skipped")),
+        }
+    }
+}
+
+/// O que fazer com linhas inválidas no modo tolerante
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidRowPolicy {
+    /// Mantém a linha com status `Desconhecido`
+    Unknown,
+    /// Descarta a linha
+    Skip,
+}
+
+impl Choice for InvalidRowPolicy {
+    const ALL: &'static [Self] = &[InvalidRowPolicy::Unknown, InvalidRowPolicy::Skip];
+
+    fn name(self) -> &'static str {
+        match self {
+            InvalidRowPolicy::Unknown => "unknown",
+            InvalidRowPolicy::Skip => "skip",
+        }
+    }
+}
+
+/// Modo de leitura: o estrito aborta no primeiro erro, o tolerante
+/// registra um `ParseDiagnostic` por linha e continua
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    #[default]
+    Strict,
+    Lenient(InvalidRowPolicy),
+}
@@ -1,5 +1,7 @@
 pub mod csv_parser;
+pub mod diagnostic;
 pub mod zabbix_parser;
 
 pub use csv_parser::CsvParser;
+pub use diagnostic::{DiagnosticReason, InvalidRowPolicy, ParseDiagnostic, ParseMode};
 pub use zabbix_parser::ZabbixParser;
\ No newline at end of file
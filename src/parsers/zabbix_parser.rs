@@ -1,31 +1,57 @@
 use crate::{
-    config::{REGIONAL_PREFIXES, STATUS_ATIVO, STATUS_DEFEITO, STATUS_DISPONIVEL, 
-             STATUS_LITIGIO, STATUS_NOMADICO},
-    error::Result,
+    config::Config,
+    error::{AppError, Result},
+    i18n::tf,
     models::{
         common::{DataSource, EstacaoInfo, Regional, Status, TipoEstacao},
-        zabbix::{ZabbixHost, ZabbixResponse},
+        zabbix::{ZabbixHost, ZabbixResponse, ZabbixVersion},
     },
     utils::normalizer::Normalizer,
 };
 
 pub struct ZabbixParser;
 
+/// Conteúdo de um arquivo exportado de `host.get`
+#[derive(Debug, Clone)]
+pub struct ZabbixExport {
+    pub version: Option<ZabbixVersion>,
+    pub hosts: Vec<ZabbixHost>,
+}
+
 impl ZabbixParser {
     pub fn parse(json_data: &str) -> Result<Vec<ZabbixHost>> {
+        Ok(Self::parse_export(json_data)?.hosts)
+    }
+    
+    /// Lê a exportação junto com a versão da API, quando presente em
+    /// `apiinfo.version`
+    pub fn parse_export(json_data: &str) -> Result<ZabbixExport> {
         let response: ZabbixResponse = serde_json::from_str(json_data)?;
-        Ok(response.result)
+        let version = match &response.apiinfo {
+            Some(info) => Some(ZabbixVersion::parse(&info.version).ok_or_else(|| {
+                AppError::ZabbixApi(tf("error.zabbix_version", &[("version", &info.version)]))
+            })?),
+            None => None,
+        };
+        
+        let hosts = response
+            .result
+            .into_iter()
+            .map(|raw| raw.into_host(version.as_ref()))
+            .collect();
+        
+        Ok(ZabbixExport { version, hosts })
     }
     
-    pub fn to_estacao_info(hosts: Vec<ZabbixHost>) -> Result<Vec<EstacaoInfo>> {
+    pub fn to_estacao_info(hosts: Vec<ZabbixHost>, config: &Config) -> Result<Vec<EstacaoInfo>> {
         let mut estacoes = Vec::new();
         
         for host in hosts {
-            let status = Self::extract_status(&host).unwrap_or(crate::models::common::Status::Desconhecido);
-            let regional = Self::extract_regional(&host);
-            let tipo = Self::extract_tipo(&host);
+            let status = Self::extract_status(&host, config).unwrap_or(Status::Desconhecido);
+            let regional = Self::extract_regional(&host, config);
+            let tipo = Self::extract_tipo(&host, config);
 
-            let estacao = EstacaoInfo::new(
+            let mut estacao = EstacaoInfo::new(
                 host.hostid.clone(),
                 host.name.clone(),
                 status,
@@ -33,6 +59,8 @@ impl ZabbixParser {
                 tipo,
                 DataSource::Zabbix,
             );
+            estacao.zabbix_hostid = Some(host.hostid.clone());
+            estacao.ip = host.main_ip().map(str::to_string);
 
             estacoes.push(estacao);
         }
@@ -40,16 +68,11 @@ impl ZabbixParser {
         Ok(estacoes)
     }
     
-    fn extract_status(host: &ZabbixHost) -> Option<Status> {
+    fn extract_status(host: &ZabbixHost, config: &Config) -> Option<Status> {
         for group in &host.groups {
             // Primeiro, tentar por groupid (configurado)
-            match group.groupid.as_str() {
-                STATUS_ATIVO => return Some(Status::Ativo),
-                STATUS_DEFEITO => return Some(Status::Defeito),
-                STATUS_DISPONIVEL => return Some(Status::Disponivel),
-                STATUS_NOMADICO => return Some(Status::Nomadico),
-                STATUS_LITIGIO => return Some(Status::Litigio),
-                _ => {}
+            if let Some(status) = config.zabbix_status(&group.groupid) {
+                return Some(status);
             }
 
             // Se não encontrou por ID, tentar inferir pelo nome do grupo (mais flexível)
@@ -69,43 +92,45 @@ impl ZabbixParser {
         None
     }
     
-    fn extract_regional(host: &ZabbixHost) -> Option<Regional> {
-        for group in &host.groups {
-            for prefix in REGIONAL_PREFIXES {
-                if group.name.starts_with(prefix) {
-                    return Some(Regional::new(group.name.clone()));
-                }
-            }
-        }
-        None
+    fn extract_regional(host: &ZabbixHost, config: &Config) -> Option<Regional> {
+        host.groups
+            .iter()
+            .find(|group| config.is_regional(&group.name))
+            .map(|group| Regional::new(group.name.clone()))
+    }
+    
+    fn extract_tipo(host: &ZabbixHost, config: &Config) -> TipoEstacao {
+        config
+            .tipo_for_name(&host.name)
+            .or_else(|| {
+                // Tentar identificar pelo grupo
+                host.groups
+                    .iter()
+                    .find_map(|group| config.tipo_for_group(&group.name))
+            })
+            .unwrap_or_else(|| TipoEstacao::Outro(host.name.clone()))
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
     
-    fn extract_tipo(host: &ZabbixHost) -> TipoEstacao {
-        let nome = &host.name;
+    #[test]
+    fn test_parse_groups_and_hostgroups() {
+        let legacy = r#"{"result": [{"hostid": "1", "host": "a", "name": "a",
+            "groups": [{"groupid": "54", "name": "Ativo"}]}]}"#;
+        let current = r#"{"apiinfo": {"version": "7.0.3"}, "result": [
+            {"hostid": "1", "host": "a", "name": "a",
+             "hostgroups": [{"groupid": "48", "name": "Defeito"}]},
+            {"hostid": "2", "host": "b", "name": "b"}]}"#;
         
-        if nome.to_lowercase().contains("rfeye") {
-            TipoEstacao::RFeye
-        } else if nome.to_lowercase().contains("miaer") {
-            TipoEstacao::MIAer
-        } else if nome.to_lowercase().contains("cwsm") {
-            TipoEstacao::CelWirelessRMU
-        } else if nome.to_lowercase().contains("ums") {
-            TipoEstacao::UMS300
-        } else if nome.to_lowercase().contains("erm") {
-            TipoEstacao::ERMx
-        } else {
-            // Tentar identificar pelo grupo
-            for group in &host.groups {
-                let group_name = group.name.to_lowercase();
-                if group_name.contains("rfeye") {
-                    return TipoEstacao::RFeye;
-                } else if group_name.contains("miaer") {
-                    return TipoEstacao::MIAer;
-                } else if group_name.contains("erm") {
-                    return TipoEstacao::ERMx;
-                }
-            }
-            TipoEstacao::Outro(nome.clone())
-        }
+        let hosts = ZabbixParser::parse(legacy).unwrap();
+        assert_eq!(hosts[0].groups[0].groupid, "54");
+        
+        let export = ZabbixParser::parse_export(current).unwrap();
+        assert_eq!(export.version, Some(ZabbixVersion::new(7, 0, 3)));
+        assert_eq!(export.hosts[0].groups[0].groupid, "48");
+        assert!(export.hosts[1].groups.is_empty());
     }
 }
\ No newline at end of file
@@ -1,175 +1,397 @@
-use crate::services::comparator::ComparisonResult;
-use colored::Colorize;
+use crate::{
+    i18n::{self, percent, t, tf},
+    models::common::{EstacaoInfo, Regional},
+    parsers::diagnostic::ParseDiagnostic,
+    reports::{
+        model::{Block, Report, Section, Span, Stat, Table, Tone},
+        render::TextRenderer,
+    },
+    services::comparator::{BrokenLinkKind, ComparisonResult, IpIssueKind},
+};
 
 pub struct DiscrepancyReport;
 
 impl DiscrepancyReport {
     pub fn generate(comparison: &ComparisonResult) -> String {
-        let mut report = String::new();
-        
-        // Cabeçalho
-        report.push_str(&Self::header());
+        TextRenderer::render_text(&Self::model(comparison))
+    }
+    
+    /// Relatório independente de formato, para qualquer `ReportRenderer`
+    pub fn model(comparison: &ComparisonResult) -> Report {
+        let mut report = Report::new(&t("discrepancy.title"))
+            .with_subtitle(&t("discrepancy.subtitle"))
+            .with_footer(tf(
+                "report.generated_at",
+                &[("date", &i18n::datetime(&chrono::Local::now()))],
+            ));
         
         // Resumo
-        report.push_str(&Self::summary_section(comparison));
+        report.push(Self::summary_section(comparison));
         
         // Discrepâncias de Status
         if !comparison.status_mismatch.is_empty() {
-            report.push_str(&Self::status_mismatch_section(comparison));
+            report.push(Self::status_mismatch_section(comparison));
+        }
+        
+        // Discrepâncias de Regional
+        if !comparison.regional_mismatch.is_empty() {
+            report.push(Self::regional_mismatch_section(comparison));
+        }
+        
+        // Todas as divergências, campo a campo
+        if !comparison.diffs.is_empty() {
+            report.push(Self::field_diff_section(comparison));
+        }
+        
+        // Links para o Zabbix quebrados
+        if !comparison.broken_links.is_empty() {
+            report.push(Self::broken_links_section(comparison));
+        }
+        
+        // IPs vazios, inválidos ou repetidos
+        if !comparison.ip_issues.is_empty() {
+            report.push(Self::ip_issues_section(comparison));
+        }
+        
+        // Registros que colidem na mesma chave
+        if !comparison.duplicates.is_empty() {
+            report.push(Self::duplicates_section(comparison));
+        }
+        
+        // Prováveis correspondências, para confirmação
+        if !comparison.probable_matches.is_empty() {
+            report.push(Self::probable_matches_section(comparison));
         }
         
         // Estações apenas no Zabbix
         if !comparison.only_in_zabbix.is_empty() {
-            report.push_str(&Self::only_in_zabbix_section(comparison));
+            report.push(Self::only_in_section(
+                "📡",
+                &t("discrepancy.only_in_zabbix"),
+                &comparison.only_in_zabbix,
+                Tone::Info,
+            ));
         }
         
         // Estações apenas no SharePoint
         if !comparison.only_in_sharepoint.is_empty() {
-            report.push_str(&Self::only_in_sharepoint_section(comparison));
+            report.push(Self::only_in_section(
+                "📝",
+                &t("discrepancy.only_in_sharepoint"),
+                &comparison.only_in_sharepoint,
+                Tone::Good,
+            ));
         }
         
-        // Rodapé
-        report.push_str(&Self::footer());
+        // Divergências aceitas, recolhidas ao fim
+        if !comparison.accepted.is_empty() || !comparison.expired_waivers.is_empty() {
+            report.push(Self::accepted_section(comparison));
+        }
+        
+        // Erros de leitura (modo tolerante)
+        if !comparison.diagnostics.is_empty() {
+            report.push(Self::diagnostics_section("discrepancy.read_errors", &comparison.diagnostics));
+        }
+        if !comparison.unattributed_diagnostics.is_empty() {
+            report.push(Self::diagnostics_section(
+                "discrepancy.unattributed_read_errors",
+                &comparison.unattributed_diagnostics,
+            ));
+        }
         
         report
     }
     
-    fn header() -> String {
-        let mut header = String::new();
-        header.push_str(&"=".repeat(80));
-        header.push('\n');
-        header.push_str(&format!("{:^80}", "RELATÓRIO DE DISCREPÂNCIAS"));
-        header.push('\n');
-        header.push_str(&format!("{:^80}", "Zabbix vs SharePoint"));
-        header.push('\n');
-        header.push_str(&"=".repeat(80));
-        header.push_str("\n\n");
-        header
+    fn summary_section(comparison: &ComparisonResult) -> Section {
+        let mut stats = vec![
+            Self::count("✅", "discrepancy.matching", comparison.matching.len()),
+            Self::count("⚠️ ", "discrepancy.status_mismatch", comparison.status_mismatch.len()),
+            Self::count("🏢", "discrepancy.regional_mismatch", comparison.regional_mismatch.len()),
+            Self::count("🔍", "discrepancy.with_diffs", comparison.diffs.len()),
+        ];
+        let optional = [
+            ("🔗", "discrepancy.broken_links", comparison.broken_links.len()),
+            ("🌐", "discrepancy.ip_issues", comparison.ip_issues.len()),
+            ("👥", "discrepancy.duplicate_keys", comparison.duplicates.len()),
+            ("🤝", "discrepancy.probable_matches", comparison.probable_matches.len()),
+        ];
+        stats.extend(
+            optional
+                .into_iter()
+                .filter(|(_, _, count)| *count > 0)
+                .map(|(icon, key, count)| Self::count(icon, key, count)),
+        );
+        stats.push(Self::count("📡", "discrepancy.only_in_zabbix", comparison.only_in_zabbix.len()));
+        stats.push(Self::count("📝", "discrepancy.only_in_sharepoint", comparison.only_in_sharepoint.len()));
+        let optional = [
+            ("📦", "discrepancy.expected_absent", comparison.expected_absent.len()),
+            ("🛡️ ", "discrepancy.accepted", comparison.accepted.len()),
+            ("⌛", "discrepancy.expired_waivers", comparison.expired_waivers.len()),
+            ("🩺", "discrepancy.diagnostics", comparison.diagnostics.len()),
+            ("🩺", "discrepancy.unattributed", comparison.unattributed_diagnostics.len()),
+        ];
+        stats.extend(
+            optional
+                .into_iter()
+                .filter(|(_, _, count)| *count > 0)
+                .map(|(icon, key, count)| Self::count(icon, key, count)),
+        );
+        
+        Section::new("📊", &t("discrepancy.summary")).with(Block::Stats { stats })
     }
     
-    fn summary_section(comparison: &ComparisonResult) -> String {
-        let mut summary = String::new();
-        summary.push_str("📊 RESUMO\n");
-        summary.push_str(&"-".repeat(40));
-        summary.push('\n');
-        
-        summary.push_str(&format!(
-            "  ✅ Estações correspondentes: {}\n",
-            comparison.matching.len()
-        ));
-        summary.push_str(&format!(
-            "  ⚠️  Discrepâncias de status: {}\n",
-            comparison.status_mismatch.len()
-        ));
-        summary.push_str(&format!(
-            "  📡 Apenas no Zabbix: {}\n",
-            comparison.only_in_zabbix.len()
-        ));
-        summary.push_str(&format!(
-            "  📝 Apenas no SharePoint: {}\n",
-            comparison.only_in_sharepoint.len()
-        ));
-        summary.push_str("\n\n");
-        summary
+    /// Contagem do resumo com rótulo do catálogo e separador de milhar
+    fn count(icon: &str, key: &str, count: usize) -> Stat {
+        Stat::new(icon, &t(key), i18n::number(count))
     }
     
-    fn status_mismatch_section(comparison: &ComparisonResult) -> String {
-        let mut section = String::new();
-        section.push_str("⚠️  DISCREPÂNCIAS DE STATUS\n");
-        section.push_str(&"-".repeat(40));
-        section.push('\n');
+    fn status_mismatch_section(comparison: &ComparisonResult) -> Section {
+        let mut table = Self::table(&[
+            "column.id",
+            "column.name",
+            "column.status_zabbix",
+            "column.status_sharepoint",
+            "column.regional_zabbix",
+            "column.regional_sharepoint",
+        ]);
+        let regional = |r: &Option<Regional>| {
+            r.as_ref().map(|r| r.to_string()).unwrap_or_else(|| t("report.not_available"))
+        };
         
         for mismatch in &comparison.status_mismatch {
-            section.push_str(&format!(
-                "\n  ID: {}\n",
-                mismatch.id.yellow()
-            ));
-            section.push_str(&format!(
-                "  Nome: {}\n",
-                mismatch.nome
-            ));
-            section.push_str(&format!(
-                "  Status Zabbix: {} | Status SharePoint: {}\n",
-                format!("{}", mismatch.zabbix_status).red(),
-                format!("{}", mismatch.sharepoint_status).blue()
-            ));
-            
-            if mismatch.zabbix_regional != mismatch.sharepoint_regional {
-                section.push_str(&format!(
-                    "  Regional Zabbix: {} | Regional SharePoint: {}\n",
-                    mismatch.zabbix_regional
-                        .as_ref()
-                        .map(|r| r.to_string())
-                        .unwrap_or_else(|| "N/A".to_string()),
-                    mismatch.sharepoint_regional
+            table.row(vec![
+                Span::new(&mismatch.id, Tone::Key),
+                Span::plain(&mismatch.nome),
+                Span::new(&mismatch.zabbix_status, Tone::Bad),
+                Span::new(&mismatch.sharepoint_status, Tone::Accent),
+                Span::plain(regional(&mismatch.zabbix_regional)),
+                Span::plain(regional(&mismatch.sharepoint_regional)),
+            ]);
+        }
+        Section::new("⚠️ ", &t("discrepancy.status_mismatch")).with(Block::Table(table))
+    }
+    
+    fn regional_mismatch_section(comparison: &ComparisonResult) -> Section {
+        let mut table = Self::table(&[
+            "column.id",
+            "column.name",
+            "column.regional_zabbix",
+            "column.regional_sharepoint",
+            "column.status",
+        ]);
+        
+        for mismatch in &comparison.regional_mismatch {
+            // O status só interessa quando é o mesmo nas duas origens
+            let status = if mismatch.zabbix_status == mismatch.sharepoint_status {
+                mismatch.zabbix_status.to_string()
+            } else {
+                String::new()
+            };
+            let (zabbix, sharepoint) = mismatch.regionals();
+            table.row(vec![
+                Span::new(&mismatch.id, Tone::Key),
+                Span::plain(&mismatch.nome),
+                Span::new(zabbix, Tone::Bad),
+                Span::new(sharepoint, Tone::Accent),
+                Span::plain(status),
+            ]);
+        }
+        Section::new("🏢", &t("discrepancy.regional_mismatch")).with(Block::Table(table))
+    }
+    
+    fn field_diff_section(comparison: &ComparisonResult) -> Section {
+        let mut table = Table::new(&[&t("column.station"), &t("column.field"), "Zabbix", "SharePoint"]);
+        
+        for diff in &comparison.diffs {
+            for (index, field) in diff.fields.iter().enumerate() {
+                // O ID só aparece na primeira linha de cada estação
+                let id = if index == 0 { diff.id.as_str() } else { "" };
+                table.row(vec![
+                    Span::new(id, Tone::Key),
+                    Span::plain(field.field),
+                    Span::new(&field.zabbix_value, Tone::Bad),
+                    Span::new(&field.sharepoint_value, Tone::Accent),
+                ]);
+            }
+        }
+        Section::new("🔍", &t("discrepancy.field_diffs")).with(Block::Table(table))
+    }
+    
+    fn broken_links_section(comparison: &ComparisonResult) -> Section {
+        let mut table = Table::new(&[&t("column.id"), "hostid", &t("column.problem")]);
+        
+        for link in &comparison.broken_links {
+            let problem = match &link.kind {
+                BrokenLinkKind::HostNotFound => t("discrepancy.host_not_found"),
+                BrokenLinkKind::NameMismatch(nome) => tf("discrepancy.name_mismatch", &[("nome", nome)]),
+                BrokenLinkKind::AlreadyLinked(other) => tf("discrepancy.already_linked", &[("other", other)]),
+            };
+            table.row(vec![
+                Span::new(&link.id, Tone::Key),
+                Span::plain(&link.hostid),
+                Span::new(problem, Tone::Bad),
+            ]);
+        }
+        Section::new("🔗", &t("discrepancy.broken_links")).with(Block::Table(table))
+    }
+    
+    fn ip_issues_section(comparison: &ComparisonResult) -> Section {
+        let mut table = Self::table(&["column.name", "column.id", "column.source", "column.problem"]);
+        
+        for issue in &comparison.ip_issues {
+            let ip = issue.ip.clone().unwrap_or_else(|| t("report.not_available"));
+            let problem = match &issue.kind {
+                IpIssueKind::Empty => t("discrepancy.ip_empty"),
+                IpIssueKind::Invalid => tf("discrepancy.ip_invalid", &[("ip", &ip)]),
+                IpIssueKind::Duplicate(others) => {
+                    tf("discrepancy.ip_duplicate", &[("ip", &ip), ("others", &others.join(", "))])
+                }
+            };
+            table.row(vec![
+                Span::new(&issue.nome, Tone::Key),
+                Span::plain(&issue.id),
+                Span::plain(issue.source),
+                Span::new(problem, Tone::Bad),
+            ]);
+        }
+        Section::new("🌐", &t("discrepancy.ip_issues")).with(Block::Table(table))
+    }
+    
+    fn duplicates_section(comparison: &ComparisonResult) -> Section {
+        let mut table = Self::table(&["column.key", "column.source", "column.name", "column.id", "column.status"]);
+        
+        for duplicate in &comparison.duplicates {
+            for (index, record) in duplicate.records.iter().enumerate() {
+                // Chave e origem só na primeira linha de cada grupo
+                let (key, source) = if index == 0 {
+                    (duplicate.key.clone(), duplicate.source.to_string())
+                } else {
+                    (String::new(), String::new())
+                };
+                table.row(vec![
+                    Span::new(key, Tone::Key),
+                    Span::plain(source),
+                    Span::plain(&record.nome),
+                    Span::plain(&record.id),
+                    Span::plain(&record.status),
+                ]);
+            }
+        }
+        Section::new("👥", &t("discrepancy.duplicates")).with(Block::Table(table))
+    }
+    
+    fn probable_matches_section(comparison: &ComparisonResult) -> Section {
+        let mut table = Table::new(&[
+            "Zabbix",
+            &t("column.zabbix_id"),
+            "SharePoint",
+            &t("column.confidence"),
+            &t("column.status_zabbix"),
+            &t("column.status_sharepoint"),
+        ]);
+        
+        for probable in &comparison.probable_matches {
+            table.row(vec![
+                Span::new(&probable.zabbix.nome, Tone::Bad),
+                Span::plain(&probable.zabbix.id),
+                Span::new(&probable.sharepoint.id, Tone::Accent),
+                Span::plain(percent(probable.confidence * 100.0, 0)),
+                Span::plain(&probable.zabbix.status),
+                Span::plain(&probable.sharepoint.status),
+            ]);
+        }
+        Section::new("🤝", &t("discrepancy.probable_confirm")).with(Block::Table(table))
+    }
+    
+    fn only_in_section(
+        icon: &str,
+        title: &str,
+        estacoes: &[EstacaoInfo],
+        tone: Tone,
+    ) -> Section {
+        let mut table = Self::table(&["column.name", "column.id", "column.status", "column.regional"]);
+        
+        for estacao in estacoes {
+            table.row(vec![
+                Span::new(&estacao.nome, tone),
+                Span::plain(&estacao.id),
+                Span::plain(&estacao.status),
+                Span::plain(
+                    estacao.regional
                         .as_ref()
                         .map(|r| r.to_string())
-                        .unwrap_or_else(|| "N/A".to_string())
-                ));
-            }
+                        .unwrap_or_else(|| t("report.not_available")),
+                ),
+            ]);
         }
-        section.push_str("\n\n");
-        section
+        Section::new(icon, title).with(Block::Table(table))
     }
     
-    fn only_in_zabbix_section(comparison: &ComparisonResult) -> String {
-        let mut section = String::new();
-        section.push_str("📡 APENAS NO ZABBIX\n");
-        section.push_str(&"-".repeat(40));
-        section.push('\n');
-        
-        for estacao in &comparison.only_in_zabbix {
-            section.push_str(&format!(
-                "\n  • {} ({})\n",
-                estacao.nome.cyan(),
-                estacao.id
-            ));
-            section.push_str(&format!(
-                "    Status: {} | Regional: {}\n",
-                estacao.status,
-                estacao.regional
-                    .as_ref()
-                    .map(|r| r.to_string())
-                    .unwrap_or_else(|| "N/A".to_string())
-            ));
+    fn accepted_section(comparison: &ComparisonResult) -> Section {
+        let mut section = Section::new(
+            "🛡️ ",
+            &tf("discrepancy.accepted_count", &[("count", &i18n::number(comparison.accepted.len()))]),
+        );
+        
+        if !comparison.accepted.is_empty() {
+            let mut table = Self::table(&[
+                "column.station",
+                "column.category",
+                "column.detail",
+                "column.justification",
+                "column.owner",
+                "column.until",
+            ]);
+            for item in &comparison.accepted {
+                table.row(vec![
+                    Span::plain(&item.station),
+                    Span::plain(item.category),
+                    Span::new(item.detail(), Tone::Muted),
+                    Span::plain(&item.waiver.justification),
+                    Span::plain(&item.waiver.owner),
+                    Span::plain(i18n::date(item.waiver.expires)),
+                ]);
+            }
+            section = section.with(Block::Table(table));
+        }
+        
+        if !comparison.expired_waivers.is_empty() {
+            let mut table = Self::table(&[
+                "column.expired_waiver",
+                "column.category",
+                "column.expired_on",
+                "column.owner",
+            ]);
+            for waiver in &comparison.expired_waivers {
+                table.row(vec![
+                    Span::new(&waiver.station, Tone::Warning),
+                    Span::plain(waiver.category()),
+                    Span::plain(i18n::date(waiver.expires)),
+                    Span::plain(&waiver.owner),
+                ]);
+            }
+            section = section.with(Block::Table(table));
         }
-        section.push_str("\n\n");
         section
     }
     
-    fn only_in_sharepoint_section(comparison: &ComparisonResult) -> String {
-        let mut section = String::new();
-        section.push_str("📝 APENAS NO SHAREPOINT\n");
-        section.push_str(&"-".repeat(40));
-        section.push('\n');
-        
-        for estacao in &comparison.only_in_sharepoint {
-            section.push_str(&format!(
-                "\n  • {} ({})\n",
-                estacao.nome.green(),
-                estacao.id
-            ));
-            section.push_str(&format!(
-                "    Status: {} | Regional: {}\n",
-                estacao.status,
-                estacao.regional
-                    .as_ref()
-                    .map(|r| r.to_string())
-                    .unwrap_or_else(|| "N/A".to_string())
-            ));
+    fn diagnostics_section(title: &str, diagnostics: &[ParseDiagnostic]) -> Section {
+        let mut table = Self::table(&["column.row", "column.column", "column.value", "column.reason"]);
+        
+        for diagnostic in diagnostics {
+            table.row(vec![
+                Span::new(diagnostic.row, Tone::Key),
+                Span::plain(&diagnostic.column),
+                Span::plain(format!("\"{}\"", diagnostic.value)),
+                Span::plain(diagnostic.reason.to_string()),
+            ]);
         }
-        section.push_str("\n\n");
-        section
+        Section::new("🩺", &t(title)).with(Block::Table(table))
     }
     
-    fn footer() -> String {
-        let mut footer = String::new();
-        footer.push_str(&"=".repeat(80));
-        footer.push('\n');
-        footer.push_str(&format!(
-            "Gerado em: {}\n",
-            chrono::Local::now().format("%d/%m/%Y %H:%M:%S")
-        ));
-        footer
+    /// Tabela com os cabeçalhos traduzidos a partir das chaves do catálogo
+    fn table(keys: &[&str]) -> Table {
+        let headers: Vec<String> = keys.iter().map(|key| t(key)).collect();
+        Table::new(&headers.iter().map(String::as_str).collect::<Vec<_>>())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,111 @@
+use crate::{
+    i18n::{self, t, tf},
+    reports::{
+        model::{Block, Report, Section, Span, Stat, Table, Tone},
+        render::TextRenderer,
+    },
+    services::history::{AgedDiscrepancy, HistoryDiff},
+};
+
+pub struct HistoryReport;
+
+impl HistoryReport {
+    pub fn generate(diff: &HistoryDiff) -> String {
+        TextRenderer::render_text(&Self::model(diff))
+    }
+
+    /// Relatório independente de formato, para qualquer `ReportRenderer`
+    pub fn model(diff: &HistoryDiff) -> Report {
+        let subtitle = format!("{} → {}", i18n::datetime_short(&diff.from), i18n::datetime_short(&diff.to));
+        let mut report = Report::new(&t("history.title")).with_subtitle(&subtitle);
+
+        report.push(Self::summary_section(diff));
+
+        if !diff.appeared.is_empty() {
+            report.push(Self::section("🆕", "history.appeared_title", &diff.appeared));
+        }
+        if !diff.resolved.is_empty() {
+            report.push(Self::section("✅", "history.resolved_title", &diff.resolved));
+        }
+        if !diff.persisted.is_empty() {
+            report.push(Self::section("⏳", "history.persisted_title", &diff.persisted));
+        }
+
+        report
+    }
+
+    fn summary_section(diff: &HistoryDiff) -> Section {
+        let stats = vec![
+            Stat::new("🆕", &t("history.appeared"), Span::new(i18n::number(diff.appeared.len()), Tone::Bad)),
+            Stat::new("✅", &t("history.resolved"), Span::new(i18n::number(diff.resolved.len()), Tone::Good)),
+            Stat::new("⏳", &t("history.persisted"), i18n::number(diff.persisted.len())),
+        ];
+        Section::new("📊", &t("history.summary")).with(Block::Stats { stats })
+    }
+
+    fn section(icon: &str, title: &str, items: &[AgedDiscrepancy]) -> Section {
+        let mut table = Table::new(&[
+            &t("column.station"),
+            &t("column.category"),
+            &t("column.description"),
+            &t("column.age"),
+            &t("column.since"),
+        ]);
+
+        for item in items {
+            table.row(vec![
+                Span::new(&item.discrepancy.station, Tone::Key),
+                Span::plain(item.discrepancy.category),
+                Span::plain(&item.discrepancy.description),
+                Span::plain(Self::age(item.age_days)),
+                Span::new(i18n::date(item.since.date_naive()), Tone::Muted),
+            ]);
+        }
+        Section::new(icon, &t(title)).with(Block::Table(table))
+    }
+
+    fn age(days: i64) -> String {
+        match days {
+            0 => t("history.age_zero"),
+            1 => t("history.age_one"),
+            n => tf("history.age_many", &[("days", &n)]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reports::{markdown::MarkdownRenderer, render::ReportRenderer};
+    use crate::services::comparator::{Discrepancy, DiscrepancyCategory};
+    use chrono::{Duration, Local};
+
+    #[test]
+    fn test_model_sections() {
+        let to = Local::now();
+        let item = AgedDiscrepancy {
+            discrepancy: Discrepancy {
+                category: DiscrepancyCategory::Status,
+                station: "RFeye002300".to_string(),
+                field: None,
+                description: "Status diferente".to_string(),
+            },
+            since: to - Duration::days(3),
+            age_days: 3,
+        };
+        let diff = HistoryDiff {
+            from: to - Duration::days(1),
+            to,
+            appeared: Vec::new(),
+            resolved: Vec::new(),
+            persisted: vec![item],
+        };
+
+        let report = HistoryReport::model(&diff);
+        let titles: Vec<&str> = report.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, ["RESUMO", "PERSISTENTES"]);
+
+        let markdown = MarkdownRenderer.render(&report).unwrap();
+        assert!(markdown.contains("| RFeye002300 | Status | Status diferente | 3 dias |"));
+    }
+}
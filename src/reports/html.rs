@@ -0,0 +1,369 @@
+//! Painel HTML autocontido (CSS, JavaScript e gráficos SVG embutidos),
+//! para revisar a conciliação no navegador, sem acesso ao terminal.
+
+use crate::{
+    i18n::{self, t, tf},
+    models::common::DataSource,
+    error::Result,
+    reports::{
+        model::{Block, Report, Stat},
+        render::ReportRenderer,
+        summary::StatusRegionalMatrix,
+        table::{detail_headers, TableReport},
+        SummaryReport,
+    },
+    services::{
+        aggregator::AggregatedData,
+        comparator::{ComparisonResult, DiscrepancyCategory},
+    },
+    utils::choice::Choice,
+};
+
+const STYLE: &str = r#"
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; background: #fafafa; }
+h1 { margin-bottom: 0; }
+.generated { color: #666; margin-top: .25rem; }
+.cards { display: flex; flex-wrap: wrap; gap: 1rem; margin: 1.5rem 0; }
+.card { background: #fff; border: 1px solid #ddd; border-radius: 6px; padding: .75rem 1rem; min-width: 9rem; }
+.card .value { font-size: 1.6rem; font-weight: bold; }
+.card .label { color: #555; font-size: .85rem; }
+section { background: #fff; border: 1px solid #ddd; border-radius: 6px; padding: 1rem; margin-bottom: 1.5rem; }
+table { border-collapse: collapse; width: 100%; font-size: .9rem; }
+th, td { border-bottom: 1px solid #eee; padding: .3rem .5rem; text-align: left; }
+th.sortable { cursor: pointer; user-select: none; background: #f0f0f0; }
+th.sortable::after { content: " ⇅"; color: #999; }
+td.num, th.num { text-align: right; }
+.heatmap td { text-align: center; min-width: 3rem; }
+input.filter { margin-bottom: .5rem; padding: .3rem; width: 20rem; max-width: 100%; }
+svg text { font-size: 12px; fill: #333; }
+"#;
+
+const SCRIPT: &str = r#"
+document.querySelectorAll("table.sortable").forEach(function (table) {
+  table.querySelectorAll("th").forEach(function (th, col) {
+    th.classList.add("sortable");
+    th.addEventListener("click", function () {
+      var body = table.tBodies[0];
+      var asc = th.dataset.order !== "asc";
+      th.dataset.order = asc ? "asc" : "desc";
+      Array.from(body.rows)
+        .sort(function (a, b) {
+          var x = a.cells[col].textContent, y = b.cells[col].textContent;
+          var nx = parseFloat(x), ny = parseFloat(y);
+          var cmp = !isNaN(nx) && !isNaN(ny) ? nx - ny : x.localeCompare(y, document.documentElement.lang);
+          return asc ? cmp : -cmp;
+        })
+        .forEach(function (row) { body.appendChild(row); });
+    });
+  });
+});
+document.querySelectorAll("input.filter").forEach(function (input) {
+  var table = document.getElementById(input.dataset.table);
+  input.addEventListener("input", function () {
+    var term = input.value.toLowerCase();
+    Array.from(table.tBodies[0].rows).forEach(function (row) {
+      row.style.display = row.textContent.toLowerCase().includes(term) ? "" : "none";
+    });
+  });
+});
+"#;
+
+pub struct HtmlReport;
+
+/// Página genérica de qualquer relatório do modelo, para os comandos sem
+/// painel próprio (`trend`, `history diff`)
+impl ReportRenderer for HtmlReport {
+    fn render(&self, report: &Report) -> Result<String> {
+        let mut body = String::new();
+        if let Some(subtitle) = &report.subtitle {
+            body.push_str(&format!("<p>{}</p>\n", escape(subtitle)));
+        }
+
+        for (index, section) in report.sections.iter().enumerate() {
+            let mut content = String::new();
+            for block in &section.blocks {
+                match block {
+                    Block::Stats { stats } => content.push_str(&Self::stat_cards(stats)),
+                    Block::Table(table) => {
+                        let rows: Vec<Vec<String>> = table
+                            .rows
+                            .iter()
+                            .map(|row| row.iter().map(|span| span.text.clone()).collect())
+                            .collect();
+                        content.push_str(&Self::table(&format!("secao-{}", index), &table.headers, &rows));
+                    }
+                }
+            }
+            body.push_str(&Self::section(&section.title, &content));
+        }
+
+        if let Some(footer) = &report.footer {
+            body.push_str(&format!("<p class=\"generated\">{}</p>\n", escape(footer)));
+        }
+        Ok(Self::page(&report.title, &body))
+    }
+}
+
+impl HtmlReport {
+    /// Painel da comparação: contagens, gráfico por categoria, matriz
+    /// status × regional das estações da origem indicada e uma tabela por
+    /// categoria de discrepância
+    pub fn comparison(comparison: &ComparisonResult, stations: &AggregatedData, source: DataSource) -> String {
+        let rows = TableReport::rows(comparison);
+        let mut body = String::new();
+
+        let mut cards = vec![
+            (t("html.matching"), comparison.matching.len()),
+            (t("html.discrepancies"), rows.len()),
+        ];
+        if !comparison.accepted.is_empty() {
+            cards.push((t("html.accepted"), comparison.accepted.len()));
+        }
+        if !comparison.expected_absent.is_empty() {
+            cards.push((t("html.expected_absent"), comparison.expected_absent.len()));
+        }
+        if !comparison.diagnostics.is_empty() {
+            cards.push((t("html.read_errors"), comparison.diagnostics.len()));
+        }
+        if !comparison.unattributed_diagnostics.is_empty() {
+            cards.push((t("html.unattributed"), comparison.unattributed_diagnostics.len()));
+        }
+        body.push_str(&Self::cards(&cards));
+
+        let by_category: Vec<(String, usize)> = DiscrepancyCategory::ALL
+            .iter()
+            .map(|c| (c.to_string(), rows.iter().filter(|r| r.category == *c).count()))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        if !by_category.is_empty() {
+            body.push_str(&Self::section(&t("html.by_category"), &Self::bar_chart(&by_category)));
+        }
+
+        body.push_str(&Self::section(
+            &tf("html.matrix_of", &[("source", &source)]),
+            &Self::heatmap(&SummaryReport::matrix(stations)),
+        ));
+
+        let headers = detail_headers();
+        for (index, category) in DiscrepancyCategory::ALL.iter().enumerate() {
+            let category_rows: Vec<Vec<String>> = rows
+                .iter()
+                .filter(|r| r.category == *category)
+                .map(|r| r.detail_cells())
+                .collect();
+            if category_rows.is_empty() {
+                continue;
+            }
+            let table = Self::table(&format!("categoria-{}", index), &headers, &category_rows);
+            body.push_str(&Self::section(
+                &format!("{} ({})", category, i18n::number(category_rows.len())),
+                &table,
+            ));
+        }
+
+        Self::page(&t("html.comparison_title"), &body)
+    }
+
+    /// Painel resumido de uma origem: contagens, gráficos por status e
+    /// regional e a matriz status × regional
+    pub fn summary(title: &str, data: &AggregatedData) -> String {
+        let mut body = String::new();
+
+        let mut cards = vec![(t("summary.total"), data.totals.total)];
+        if data.totals.without_regional > 0 {
+            cards.push((t("summary.without_regional"), data.totals.without_regional));
+        }
+        body.push_str(&Self::cards(&cards));
+
+        let mut by_status: Vec<(String, usize)> = data
+            .totals
+            .by_status
+            .iter()
+            .map(|(status, count)| (status.to_string(), *count))
+            .collect();
+        by_status.sort();
+        body.push_str(&Self::section(&t("summary.by_status"), &Self::bar_chart(&by_status)));
+
+        let mut by_regional: Vec<(String, usize)> = data
+            .totals
+            .by_regional
+            .iter()
+            .map(|(regional, count)| (regional.clone(), *count))
+            .collect();
+        by_regional.sort();
+        body.push_str(&Self::section(&t("summary.by_regional"), &Self::bar_chart(&by_regional)));
+
+        body.push_str(&Self::section(
+            &t("summary.matrix"),
+            &Self::heatmap(&SummaryReport::matrix(data)),
+        ));
+
+        Self::page(title, &body)
+    }
+
+    fn page(title: &str, body: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"{lang}\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+             <h1>{title}</h1>\n<p class=\"generated\">{generated}</p>\n{body}\
+             <script>{SCRIPT}</script>\n</body>\n</html>\n",
+            lang = i18n::lang().code(),
+            title = escape(title),
+            generated = escape(&tf("html.generated_at", &[("date", &i18n::datetime(&chrono::Local::now()))])),
+        )
+    }
+
+    fn cards(cards: &[(String, usize)]) -> String {
+        let mut html = String::from("<div class=\"cards\">\n");
+        for (label, value) in cards {
+            html.push_str(&format!(
+                "<div class=\"card\"><div class=\"value\">{}</div><div class=\"label\">{}</div></div>\n",
+                i18n::number(*value),
+                escape(label)
+            ));
+        }
+        html.push_str("</div>\n");
+        html
+    }
+
+    /// Cartões com valores já formatados pelo modelo
+    fn stat_cards(stats: &[Stat]) -> String {
+        let mut html = String::from("<div class=\"cards\">\n");
+        for stat in stats {
+            html.push_str(&format!(
+                "<div class=\"card\"><div class=\"value\">{}</div><div class=\"label\">{}</div></div>\n",
+                escape(&stat.value.text),
+                escape(&stat.label)
+            ));
+        }
+        html.push_str("</div>\n");
+        html
+    }
+
+    fn section(title: &str, content: &str) -> String {
+        format!("<section>\n<h2>{}</h2>\n{}</section>\n", escape(title), content)
+    }
+
+    /// Tabela ordenável (clique no cabeçalho) com campo de filtro
+    fn table(id: &str, headers: &[String], rows: &[Vec<String>]) -> String {
+        let mut html = format!(
+            "<input class=\"filter\" type=\"search\" placeholder=\"{filter}\" data-table=\"{id}\">\n\
+             <table class=\"sortable\" id=\"{id}\">\n<thead><tr>",
+            filter = escape(&t("html.filter")),
+        );
+        for header in headers {
+            html.push_str(&format!("<th>{}</th>", escape(header)));
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+        for row in rows {
+            html.push_str("<tr>");
+            for cell in row {
+                html.push_str(&format!("<td>{}</td>", escape(cell)));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</tbody>\n</table>\n");
+        html
+    }
+
+    /// Gráfico de barras horizontais em SVG
+    fn bar_chart(bars: &[(String, usize)]) -> String {
+        const LABEL_WIDTH: usize = 180;
+        const BAR_WIDTH: usize = 420;
+        const ROW_HEIGHT: usize = 24;
+
+        let max = bars.iter().map(|(_, v)| *v).max().unwrap_or(0).max(1);
+        let height = bars.len() * ROW_HEIGHT;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" role=\"img\">\n",
+            LABEL_WIDTH + BAR_WIDTH + 60,
+            height
+        );
+        for (i, (label, value)) in bars.iter().enumerate() {
+            let y = i * ROW_HEIGHT;
+            let width = value * BAR_WIDTH / max;
+            svg.push_str(&format!(
+                "<text x=\"0\" y=\"{}\">{}</text>\
+                 <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#4a7bd0\"/>\
+                 <text x=\"{}\" y=\"{}\">{}</text>\n",
+                y + 16,
+                escape(label),
+                LABEL_WIDTH,
+                y + 4,
+                width,
+                ROW_HEIGHT - 8,
+                LABEL_WIDTH + width + 6,
+                y + 16,
+                value
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Matriz com a cor de cada célula proporcional à contagem
+    fn heatmap(matrix: &StatusRegionalMatrix) -> String {
+        let max = matrix.max().max(1);
+        let mut html = String::from("<table class=\"heatmap\">\n<thead><tr><th></th>");
+        for regional in &matrix.regionals {
+            html.push_str(&format!("<th>{}</th>", escape(regional)));
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+        for (status, row) in matrix.statuses.iter().zip(&matrix.counts) {
+            html.push_str(&format!("<tr><th>{}</th>", escape(&status.to_string())));
+            for count in row {
+                // Azul com opacidade entre 0.1 e 0.9; zero fica sem cor
+                let style = if *count == 0 {
+                    String::new()
+                } else {
+                    let alpha = 0.1 + 0.8 * (*count as f64 / max as f64);
+                    format!(" style=\"background: rgba(74, 123, 208, {:.2})\"", alpha)
+                };
+                let text = if *count == 0 { "-".to_string() } else { count.to_string() };
+                html.push_str(&format!("<td{}>{}</td>", style, text));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</tbody>\n</table>\n");
+        html
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::common::{estacao, DataSource, Status};
+    use crate::services::aggregator::Aggregator;
+    use crate::services::comparator::Comparator;
+
+    #[test]
+    fn test_comparison_dashboard() {
+        let sharepoint = vec![estacao("RFeye002300", Status::Ativo, Some("GR01"), DataSource::SharePoint)];
+        let stations = Aggregator::group_by_status_and_regional(&sharepoint);
+        let comparison = Comparator::compare(
+            vec![
+                estacao("RFeye002300", Status::Defeito, Some("GR01"), DataSource::Zabbix),
+                estacao("<script>", Status::Ativo, Some("GR01"), DataSource::Zabbix),
+            ],
+            sharepoint,
+            &Config::default(),
+        );
+
+        let html = HtmlReport::comparison(&comparison, &stations, DataSource::SharePoint);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h2>Matriz status × regional no SharePoint</h2>"));
+        assert!(html.contains("<h2>Status (1)</h2>"));
+        assert!(html.contains("<h2>Apenas no Zabbix (1)</h2>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("<svg"));
+        assert!(html.contains("rgba(74, 123, 208, 0.90)"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Saída JSON para consumo por outras ferramentas.
+//!
+//! Todo documento traz `schema_version` e `kind`; campos só são removidos
+//! ou mudam de significado com um novo `SCHEMA_VERSION`. O conteúdo não
+//! depende de `--lang` nem do locale: as descrições saem sempre em pt-BR.
+
+use crate::{
+    error::Result,
+    i18n::{self, Lang},
+    models::common::{DataSource, EstacaoInfo},
+    parsers::diagnostic::ParseDiagnostic,
+    services::{
+        aggregator::{AggregatedData, AggregationTotals},
+        comparator::{ComparisonResult, Discrepancy, DiscrepancyCategory},
+        history::HistoryDiff,
+        trend::Trend,
+    },
+};
+use chrono::{DateTime, Local};
+use indexmap::IndexMap;
+use serde::Serialize;
+
+/// Versão do esquema dos documentos JSON; ao mudá-la, atualizar também a
+/// ajuda `help.values.format.json` dos catálogos
+pub const SCHEMA_VERSION: u32 = 2;
+
+#[derive(Debug, Serialize)]
+pub struct CompareCounts {
+    /// Pares sem nenhuma divergência
+    pub matching: usize,
+    pub expected_absent: usize,
+    pub accepted: usize,
+    pub expired_waivers: usize,
+    pub diagnostics: usize,
+    pub unattributed_diagnostics: usize,
+    /// Discrepâncias por categoria; todas as categorias aparecem
+    pub categories: IndexMap<DiscrepancyCategory, usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompareDocument<'a> {
+    pub schema_version: u32,
+    pub kind: &'static str,
+    pub generated_at: DateTime<Local>,
+    pub counts: CompareCounts,
+    /// Lista plana, uma entrada por discrepância não aceita
+    pub discrepancies: Vec<Discrepancy>,
+    /// Resultado completo, com os registros de cada origem
+    pub result: &'a ComparisonResult,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StationsDocument<'a> {
+    pub schema_version: u32,
+    pub kind: &'static str,
+    pub generated_at: DateTime<Local>,
+    pub source: DataSource,
+    pub counts: &'a AggregationTotals,
+    pub estacoes: &'a [EstacaoInfo],
+    pub diagnostics: &'a [ParseDiagnostic],
+    /// Erros de leitura que o recorte não atribui a uma estação
+    pub unattributed_diagnostics: &'a [ParseDiagnostic],
+}
+
+/// Documento dos comandos cujo conteúdo já é serializável por inteiro
+#[derive(Debug, Serialize)]
+pub struct DataDocument<'a, T: Serialize> {
+    pub schema_version: u32,
+    pub kind: &'static str,
+    pub generated_at: DateTime<Local>,
+    #[serde(flatten)]
+    pub data: &'a T,
+}
+
+pub struct JsonReport;
+
+impl JsonReport {
+    pub fn compare(comparison: &ComparisonResult) -> Result<String> {
+        let discrepancies = i18n::with_lang(Lang::PtBr, || comparison.discrepancies());
+
+        let document = CompareDocument {
+            schema_version: SCHEMA_VERSION,
+            kind: "compare",
+            generated_at: Local::now(),
+            counts: CompareCounts {
+                matching: comparison.matching.len(),
+                expected_absent: comparison.expected_absent.len(),
+                accepted: comparison.accepted.len(),
+                expired_waivers: comparison.expired_waivers.len(),
+                diagnostics: comparison.diagnostics.len(),
+                unattributed_diagnostics: comparison.unattributed_diagnostics.len(),
+                categories: comparison.category_counts(),
+            },
+            discrepancies,
+            result: comparison,
+        };
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+
+    pub fn stations(
+        source: DataSource,
+        estacoes: &[EstacaoInfo],
+        aggregated: &AggregatedData,
+        diagnostics: &[ParseDiagnostic],
+        unattributed_diagnostics: &[ParseDiagnostic],
+    ) -> Result<String> {
+        let document = StationsDocument {
+            schema_version: SCHEMA_VERSION,
+            kind: "stations",
+            generated_at: Local::now(),
+            source,
+            counts: &aggregated.totals,
+            estacoes,
+            diagnostics,
+            unattributed_diagnostics,
+        };
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+
+    pub fn trend(trend: &Trend) -> Result<String> {
+        Self::data("trend", trend)
+    }
+
+    pub fn history_diff(diff: &HistoryDiff) -> Result<String> {
+        Self::data("history_diff", diff)
+    }
+
+    fn data<T: Serialize>(kind: &'static str, data: &T) -> Result<String> {
+        let document = DataDocument {
+            schema_version: SCHEMA_VERSION,
+            kind,
+            generated_at: Local::now(),
+            data,
+        };
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::common::{estacao, Status};
+    use crate::services::aggregator::Aggregator;
+    use crate::services::comparator::Comparator;
+
+    #[test]
+    fn test_compare_document() {
+        let comparison = Comparator::compare(
+            vec![
+                estacao("RFeye002300", Status::Defeito, Some("GR01"), DataSource::Zabbix),
+                estacao("RFeye002301", Status::Ativo, Some("GR01"), DataSource::Zabbix),
+            ],
+            vec![estacao("RFeye002300", Status::Ativo, Some("GR01"), DataSource::SharePoint)],
+            &Config::default(),
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&JsonReport::compare(&comparison).unwrap()).unwrap();
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+        assert_eq!(json["counts"]["matching"], 0);
+        assert_eq!(json["counts"]["categories"]["status"], 1);
+        assert_eq!(json["counts"]["categories"]["only_in_zabbix"], 1);
+        assert_eq!(json["counts"]["categories"]["broken_link"], 0);
+        assert_eq!(json["result"]["only_in_zabbix"][0]["source"], "Zabbix");
+    }
+
+    #[test]
+    fn test_compare_document_ignores_lang() {
+        let mut zabbix = estacao("RFeye002300", Status::Ativo, Some("GR01"), DataSource::Zabbix);
+        zabbix.ip = Some("10.0.0".to_string());
+        let comparison = Comparator::compare(
+            vec![zabbix],
+            vec![estacao("RFeye002300", Status::Ativo, Some("GR01"), DataSource::SharePoint)],
+            &Config::default(),
+        );
+
+        i18n::set_lang(Lang::En);
+        let json: serde_json::Value = serde_json::from_str(&JsonReport::compare(&comparison).unwrap()).unwrap();
+        assert_eq!(i18n::lang(), Lang::En);
+        i18n::set_lang(Lang::PtBr);
+        let discrepancies = json["discrepancies"].as_array().unwrap();
+        assert!(discrepancies.iter().any(|d| d["description"] == "IP inválido no Zabbix: 10.0.0"));
+    }
+
+    #[test]
+    fn test_stations_document() {
+        let estacoes = vec![estacao("RFeye002300", Status::Defeito, Some("GR01"), DataSource::SharePoint)];
+        let aggregated = Aggregator::group_by_status_and_regional(&estacoes);
+
+        let json: serde_json::Value = serde_json::from_str(
+            &JsonReport::stations(DataSource::SharePoint, &estacoes, &aggregated, &[], &[]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["counts"]["by_status"]["Defeito"], 1);
+        assert_eq!(json["estacoes"][0]["id"], "RFeye002300");
+        assert!(serde_json::to_string(&aggregated).unwrap().contains("\"regional\":\"GR01\""));
+    }
+}
@@ -0,0 +1,105 @@
+//! Relatórios em Markdown (tabelas no estilo GitHub), sem cores nem
+//! alinhamento por espaços, para colar em wiki e chamados.
+
+use crate::{
+    error::Result,
+    reports::{
+        model::{Block, Report, Span, Table, Tone},
+        render::ReportRenderer,
+    },
+};
+
+pub struct MarkdownRenderer;
+
+impl ReportRenderer for MarkdownRenderer {
+    fn render(&self, report: &Report) -> Result<String> {
+        let mut text = format!("# {}\n\n", escape(&report.title));
+        if let Some(subtitle) = &report.subtitle {
+            text.push_str(&format!("_{}_\n\n", escape(subtitle)));
+        }
+
+        for section in &report.sections {
+            text.push_str(&format!("## {}\n\n", escape(&section.title)));
+            for block in &section.blocks {
+                match block {
+                    Block::Stats { stats } => {
+                        for stat in stats {
+                            text.push_str(&format!("- {}: {}\n", escape(&stat.label), Self::span(&stat.value)));
+                        }
+                        text.push('\n');
+                    }
+                    Block::Table(table) => text.push_str(&Self::table(table)),
+                }
+            }
+        }
+
+        if let Some(footer) = &report.footer {
+            text.push_str(&format!("---\n\n{}\n", escape(footer)));
+        }
+        Ok(text)
+    }
+}
+
+impl MarkdownRenderer {
+    fn table(table: &Table) -> String {
+        let headers: Vec<String> = table.headers.iter().map(|h| escape(h)).collect();
+        let mut text = format!("| {} |\n", headers.join(" | "));
+        text.push_str(&format!("|{}\n", " --- |".repeat(headers.len())));
+        for row in &table.rows {
+            let cells: Vec<String> = row.iter().map(Self::span).collect();
+            text.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        text.push('\n');
+        text
+    }
+
+    /// Só o destaque forte sobrevive em Markdown
+    fn span(span: &Span) -> String {
+        let text = escape(&span.text);
+        match span.tone {
+            Tone::Strong if !text.is_empty() => format!("**{}**", text),
+            _ => text,
+        }
+    }
+}
+
+/// Barras verticais e quebras de linha quebrariam a tabela
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::common::{estacao, DataSource, Status};
+    use crate::reports::{DiscrepancyReport, SummaryReport};
+    use crate::services::aggregator::Aggregator;
+    use crate::services::comparator::Comparator;
+
+    #[test]
+    fn test_markdown_tables() {
+        let comparison = Comparator::compare(
+            vec![estacao("RFeye002300", Status::Defeito, Some("GR01"), DataSource::Zabbix)],
+            vec![estacao("CWSM|1", Status::Ativo, Some("GR01"), DataSource::SharePoint)],
+            &Config::default(),
+        );
+
+        let report = MarkdownRenderer.render(&DiscrepancyReport::model(&comparison)).unwrap();
+        assert!(report.starts_with("# RELATÓRIO DE DISCREPÂNCIAS\n\n_Zabbix vs SharePoint_\n\n## Resumo\n"));
+        assert!(report.contains("## Apenas no SharePoint\n\n| Nome | ID | Status | Regional |\n| --- | --- | --- | --- |\n"));
+        assert!(report.contains("| CWSM\\|1 |"));
+        assert!(!report.contains('\u{1b}'));
+
+        let summary = MarkdownRenderer
+            .render(&SummaryReport::model(&Aggregator::group_by_status_and_regional(&[estacao(
+                "A",
+                Status::Ativo,
+                Some("GR01"),
+                DataSource::Zabbix,
+            )])))
+            .unwrap();
+        assert!(summary.contains("- Total de estações: **1**\n"));
+        assert!(summary.contains("| Status | GR01 |\n| --- | --- |\n| Ativo | 1 |"));
+    }
+}
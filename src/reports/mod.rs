@@ -1,5 +1,52 @@
 pub mod discrepancy;
+pub mod history;
+pub mod html;
+pub mod json;
+pub mod markdown;
+pub mod model;
+pub mod render;
 pub mod summary;
+pub mod table;
+pub mod template;
+pub mod trend;
 
 pub use discrepancy::DiscrepancyReport;
-pub use summary::SummaryReport;
\ No newline at end of file
+pub use history::HistoryReport;
+pub use html::HtmlReport;
+pub use json::JsonReport;
+pub use markdown::MarkdownRenderer;
+pub use model::Report;
+pub use render::{ReportRenderer, TextRenderer};
+pub use summary::SummaryReport;
+pub use table::TableReport;
+pub use template::TemplateRenderer;
+pub use trend::TrendReport;
+
+use crate::utils::choice::Choice;
+
+/// Formato de saída dos relatórios
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Texto colorido para o terminal
+    #[default]
+    Text,
+    /// Documento JSON com `schema_version` e `kind` no topo
+    Json,
+    /// Painel HTML autocontido
+    Html,
+    /// Markdown com tabelas no estilo GitHub
+    Markdown,
+}
+
+impl Choice for OutputFormat {
+    const ALL: &'static [Self] = &[OutputFormat::Text, OutputFormat::Json, OutputFormat::Html, OutputFormat::Markdown];
+
+    fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Html => "html",
+            OutputFormat::Markdown => "md",
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Modelo neutro dos relatórios: seções com estatísticas e tabelas, sem
+//! nenhuma formatação. Cada formato é um `ReportRenderer` sobre ele.
+
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub sections: Vec<Section>,
+    pub footer: Option<String>,
+}
+
+impl Report {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            subtitle: None,
+            sections: Vec::new(),
+            footer: None,
+        }
+    }
+
+    pub fn with_subtitle(mut self, subtitle: &str) -> Self {
+        self.subtitle = Some(subtitle.to_string());
+        self
+    }
+
+    pub fn with_footer(mut self, footer: String) -> Self {
+        self.footer = Some(footer);
+        self
+    }
+
+    pub fn push(&mut self, section: Section) {
+        self.sections.push(section);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Section {
+    /// Emoji usado pelo texto do terminal
+    pub icon: String,
+    pub title: String,
+    pub blocks: Vec<Block>,
+}
+
+impl Section {
+    pub fn new(icon: &str, title: &str) -> Self {
+        Self {
+            icon: icon.to_string(),
+            title: title.to_string(),
+            blocks: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, block: Block) -> Self {
+        self.blocks.push(block);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Block {
+    /// Pares rótulo/valor
+    Stats { stats: Vec<Stat> },
+    Table(Table),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Stat {
+    pub icon: String,
+    pub label: String,
+    pub value: Span,
+}
+
+impl Stat {
+    pub fn new(icon: &str, label: &str, value: impl Into<Span>) -> Self {
+        Self {
+            icon: icon.to_string(),
+            label: label.to_string(),
+            value: value.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<Span>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<Span>) {
+        self.rows.push(cells);
+    }
+}
+
+/// Papel de um trecho de texto; cada formato decide como destacá-lo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tone {
+    #[default]
+    Plain,
+    Strong,
+    Muted,
+    /// Identificador de estação
+    Key,
+    Good,
+    Bad,
+    Warning,
+    Info,
+    Accent,
+    Special,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub text: String,
+    pub tone: Tone,
+}
+
+impl Span {
+    pub fn new(text: impl ToString, tone: Tone) -> Self {
+        Self {
+            text: text.to_string(),
+            tone,
+        }
+    }
+
+    pub fn plain(text: impl ToString) -> Self {
+        Self::new(text, Tone::Plain)
+    }
+}
+
+impl From<String> for Span {
+    fn from(text: String) -> Self {
+        Self::plain(text)
+    }
+}
+
+impl From<&str> for Span {
+    fn from(text: &str) -> Self {
+        Self::plain(text)
+    }
+}
+
+impl From<usize> for Span {
+    fn from(value: usize) -> Self {
+        Self::plain(value)
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
@@ -0,0 +1,136 @@
+//! Renderizadores do modelo de relatório.
+
+use crate::{
+    error::Result,
+    reports::model::{Block, Report, Section, Span, Stat, Table, Tone},
+};
+use colored::{ColoredString, Colorize};
+
+pub trait ReportRenderer {
+    fn render(&self, report: &Report) -> Result<String>;
+}
+
+/// Texto para o terminal, colorido conforme o `Tone` de cada trecho
+pub struct TextRenderer;
+
+impl ReportRenderer for TextRenderer {
+    fn render(&self, report: &Report) -> Result<String> {
+        Ok(Self::render_text(report))
+    }
+}
+
+impl TextRenderer {
+    const WIDTH: usize = 80;
+
+    pub fn render_text(report: &Report) -> String {
+        let mut text = String::new();
+
+        // Cabeçalho
+        text.push_str(&"=".repeat(Self::WIDTH));
+        text.push('\n');
+        text.push_str(&format!("{:^width$}", report.title, width = Self::WIDTH));
+        text.push('\n');
+        if let Some(subtitle) = &report.subtitle {
+            text.push_str(&format!("{:^width$}", subtitle, width = Self::WIDTH));
+            text.push('\n');
+        }
+        text.push_str(&"=".repeat(Self::WIDTH));
+        text.push_str("\n\n");
+
+        for section in &report.sections {
+            text.push_str(&Self::section(section));
+        }
+
+        // Rodapé
+        if let Some(footer) = &report.footer {
+            text.push_str(&"=".repeat(Self::WIDTH));
+            text.push('\n');
+            text.push_str(footer);
+            text.push('\n');
+        }
+        text
+    }
+
+    fn section(section: &Section) -> String {
+        let mut text = format!("{} {}\n", section.icon, section.title.to_uppercase());
+        text.push_str(&"-".repeat(40));
+        text.push('\n');
+        for block in &section.blocks {
+            match block {
+                Block::Stats { stats } => text.push_str(&Self::stats(stats)),
+                Block::Table(table) => text.push_str(&Self::table(table)),
+            }
+        }
+        text.push_str("\n\n");
+        text
+    }
+
+    fn stats(stats: &[Stat]) -> String {
+        stats
+            .iter()
+            .map(|stat| format!("  {} {}: {}\n", stat.icon, stat.label, Self::paint(&stat.value, &stat.value.text)))
+            .collect()
+    }
+
+    /// Colunas alinhadas pela célula mais larga
+    fn table(table: &Table) -> String {
+        let mut widths: Vec<usize> = table.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &table.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.text.chars().count());
+            }
+        }
+
+        let mut header = String::from(" ");
+        for (title, width) in table.headers.iter().zip(&widths) {
+            header.push_str(&format!(" {:<width$}", title, width = width));
+        }
+        let mut text = format!("\n{}\n", header.trim_end());
+        text.push_str(&format!("  {}\n", "-".repeat(widths.iter().sum::<usize>() + widths.len() - 1)));
+
+        for row in &table.rows {
+            let mut line = String::from(" ");
+            for (cell, width) in row.iter().zip(&widths) {
+                let padded = format!("{:<width$}", cell.text, width = width);
+                line.push(' ');
+                line.push_str(&Self::paint(cell, &padded).to_string());
+            }
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+
+    fn paint(span: &Span, text: &str) -> ColoredString {
+        match span.tone {
+            Tone::Plain => text.normal(),
+            Tone::Strong => text.bold(),
+            Tone::Muted => text.dimmed(),
+            Tone::Key | Tone::Warning => text.yellow(),
+            Tone::Good => text.green(),
+            Tone::Bad => text.red(),
+            Tone::Info => text.cyan(),
+            Tone::Accent => text.blue(),
+            Tone::Special => text.magenta(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_table_alignment() {
+        colored::control::set_override(false);
+        let mut table = Table::new(&["Estação", "Status"]);
+        table.row(vec![Span::new("RFeye002300", Tone::Key), "Ativo".into()]);
+        let mut report = Report::new("TÍTULO").with_footer("Rodapé".to_string());
+        report.push(Section::new("📡", "Apenas no Zabbix").with(Block::Table(table)));
+
+        let text = TextRenderer::render_text(&report);
+        assert!(text.contains("📡 APENAS NO ZABBIX\n"));
+        assert!(text.contains("\n  Estação     Status\n  ------------------\n  RFeye002300 Ativo\n"));
+        assert!(text.ends_with("Rodapé\n"));
+    }
+}
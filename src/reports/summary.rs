@@ -1,170 +1,182 @@
-use crate::services::aggregator::AggregatedData;
-use colored::Colorize;
+use crate::{
+    i18n::{self, t},
+    models::common::Status,
+    reports::{
+        model::{Block, Report, Section, Span, Stat, Table, Tone},
+        render::TextRenderer,
+    },
+    services::aggregator::AggregatedData,
+};
 
 pub struct SummaryReport;
 
+/// Contagens da matriz status × regional, na ordem de exibição
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusRegionalMatrix {
+    pub statuses: Vec<Status>,
+    pub regionals: Vec<String>,
+    /// Uma linha por status, uma coluna por regional
+    pub counts: Vec<Vec<usize>>,
+}
+
+impl StatusRegionalMatrix {
+    pub fn max(&self) -> usize {
+        self.counts.iter().flatten().copied().max().unwrap_or(0)
+    }
+}
+
 impl SummaryReport {
     pub fn generate(data: &AggregatedData) -> String {
-        let mut report = String::new();
-        
-        // Cabeçalho
-        report.push_str(&Self::header());
+        TextRenderer::render_text(&Self::model(data))
+    }
+    
+    /// Relatório independente de formato, para qualquer `ReportRenderer`
+    pub fn model(data: &AggregatedData) -> Report {
+        let mut report = Report::new(&t("summary.title"));
         
         // Estatísticas gerais
-        report.push_str(&Self::general_stats(data));
+        report.push(Self::general_stats(data));
         
         // Por Status
-        report.push_str(&Self::by_status(data));
+        report.push(Self::by_status(data));
         
         // Por Regional
-        report.push_str(&Self::by_regional(data));
+        report.push(Self::by_regional(data));
         
         // Matriz Status x Regional
-        report.push_str(&Self::status_regional_matrix(data));
+        report.push(Self::status_regional_matrix(data));
         
         report
     }
     
-    fn header() -> String {
-        let mut header = String::new();
-        header.push_str(&"═".repeat(80));
-        header.push('\n');
-        header.push_str(&format!("{:^80}", "RELATÓRIO RESUMIDO"));
-        header.push('\n');
-        header.push_str(&"═".repeat(80));
-        header.push_str("\n\n");
-        header
-    }
-    
-    fn general_stats(data: &AggregatedData) -> String {
-        let mut stats = String::new();
-        stats.push_str("📊 ESTATÍSTICAS GERAIS\n");
-        stats.push_str(&"─".repeat(40));
-        stats.push('\n');
-        stats.push_str(&format!(
-            "  Total de estações: {}\n",
-            data.totals.total.to_string().bold()
-        ));
+    fn general_stats(data: &AggregatedData) -> Section {
+        let mut stats = vec![Stat::new(
+            "•",
+            &t("summary.total"),
+            Span::new(i18n::number(data.totals.total), Tone::Strong),
+        )];
         if data.totals.without_regional > 0 {
-            stats.push_str(&format!(
-                "  Sem regional definida: {}\n",
-                data.totals.without_regional.to_string().yellow()
+            stats.push(Stat::new(
+                "•",
+                &t("summary.without_regional"),
+                Span::new(i18n::number(data.totals.without_regional), Tone::Warning),
             ));
         }
-        stats.push_str("\n\n");
-        stats
+        Section::new("📊", &t("summary.general")).with(Block::Stats { stats })
+    }
+    
+    fn percentage(count: usize, total: usize) -> f64 {
+        if total == 0 {
+            0.0
+        } else {
+            count as f64 / total as f64 * 100.0
+        }
     }
     
-    fn by_status(data: &AggregatedData) -> String {
-        let mut section = String::new();
-        section.push_str("📈 DISTRIBUIÇÃO POR STATUS\n");
-        section.push_str(&"─".repeat(40));
-        section.push('\n');
+    fn by_status(data: &AggregatedData) -> Section {
+        let mut table = Table::new(&[&t("column.status"), &t("column.total"), "%", ""]);
         
         let mut status_sorted: Vec<_> = data.totals.by_status.iter().collect();
         status_sorted.sort_by_key(|&(k, _)| format!("{:?}", k));
         
         for (status, count) in status_sorted {
-            let percentage = (*count as f64 / data.totals.total as f64) * 100.0;
+            let percentage = Self::percentage(*count, data.totals.total);
             let bar_length = (percentage / 2.0) as usize;
-            let bar = "█".repeat(bar_length);
             
-            let status_str = format!("{}", status);
-            let colored_status = match status {
-                crate::models::common::Status::Ativo => status_str.green(),
-                crate::models::common::Status::Defeito => status_str.red(),
-                crate::models::common::Status::Disponivel => status_str.cyan(),
-                crate::models::common::Status::Nomadico => status_str.yellow(),
-                crate::models::common::Status::Triagem => status_str.blue(),
-                crate::models::common::Status::Desconhecido => status_str.white(),
-                crate::models::common::Status::Manutencao => status_str.cyan(),
-                crate::models::common::Status::Litigio => status_str.magenta(),
-                crate::models::common::Status::Baixa => status_str.white(),
+            let tone = match status {
+                Status::Ativo => Tone::Good,
+                Status::Defeito => Tone::Bad,
+                Status::Disponivel | Status::Manutencao => Tone::Info,
+                Status::Nomadico => Tone::Warning,
+                Status::Triagem => Tone::Accent,
+                Status::Litigio => Tone::Special,
+                Status::Desconhecido | Status::Baixa => Tone::Plain,
             };
             
-            section.push_str(&format!(
-                "  {:<12} {:>4} ({:>5.1}%) {}\n",
-                colored_status,
-                count,
-                percentage,
-                bar.blue()
-            ));
+            table.row(vec![
+                Span::new(status, tone),
+                Span::plain(i18n::number(*count)),
+                Span::plain(i18n::percent(percentage, 1)),
+                Span::new("█".repeat(bar_length), Tone::Accent),
+            ]);
         }
-        section.push_str("\n\n");
-        section
+        Section::new("📈", &t("summary.by_status")).with(Block::Table(table))
     }
     
-    fn by_regional(data: &AggregatedData) -> String {
-        let mut section = String::new();
-        section.push_str("🌍 DISTRIBUIÇÃO POR REGIONAL\n");
-        section.push_str(&"─".repeat(40));
-        section.push('\n');
+    fn by_regional(data: &AggregatedData) -> Section {
+        let mut table = Table::new(&[&t("column.regional"), &t("column.total"), "%"]);
         
         let mut regional_sorted: Vec<_> = data.totals.by_regional.iter().collect();
         regional_sorted.sort_by_key(|&(k, _)| k);
         
         for (regional, count) in regional_sorted {
-            let percentage = (*count as f64 / data.totals.total as f64) * 100.0;
-            section.push_str(&format!(
-                "  {:<12} {:>4} ({:>5.1}%)\n",
-                regional.cyan(),
-                count,
-                percentage
-            ));
+            table.row(vec![
+                Span::new(regional, Tone::Info),
+                Span::plain(i18n::number(*count)),
+                Span::plain(i18n::percent(Self::percentage(*count, data.totals.total), 1)),
+            ]);
         }
-        section.push_str("\n\n");
-        section
+        Section::new("🌍", &t("summary.by_regional")).with(Block::Table(table))
     }
     
-    fn status_regional_matrix(data: &AggregatedData) -> String {
-        let mut section = String::new();
-        section.push_str("🔀 MATRIZ STATUS × REGIONAL\n");
-        section.push_str(&"─".repeat(40));
-        section.push('\n');
-        
+    /// Status (linhas) × regionais (colunas) com as contagens de cada
+    /// combinação
+    pub fn matrix(data: &AggregatedData) -> StatusRegionalMatrix {
         // Coletar todas as regionais únicas
-        let mut all_regionals: Vec<String> = data.by_status_and_regional
+        let mut regionals: Vec<String> = data.by_status_and_regional
             .keys()
             .map(|(_, r)| r.clone())
             .collect::<std::collections::HashSet<_>>()
             .into_iter()
             .collect();
-        all_regionals.sort();
+        regionals.sort();
         
         // Coletar todos os status únicos
-        let mut all_statuses: Vec<_> = data.by_status_and_regional
+        let mut statuses: Vec<_> = data.by_status_and_regional
             .keys()
             .map(|(s, _)| s.clone())
             .collect::<std::collections::HashSet<_>>()
             .into_iter()
             .collect();
-        all_statuses.sort_by_key(|s| format!("{:?}", s));
+        statuses.sort_by_key(|s| format!("{:?}", s));
         
-        // Criar matriz
-        section.push_str("\n");
-        section.push_str(&format!("{:<15}", ""));
-        for regional in &all_regionals {
-            section.push_str(&format!("{:<10}", &regional[..regional.len().min(9)]));
-        }
-        section.push('\n');
+        let counts = statuses
+            .iter()
+            .map(|status| {
+                regionals
+                    .iter()
+                    .map(|regional| {
+                        data.by_status_and_regional
+                            .get(&(status.clone(), regional.clone()))
+                            .map(|v| v.len())
+                            .unwrap_or(0)
+                    })
+                    .collect()
+            })
+            .collect();
         
-        for status in &all_statuses {
-            section.push_str(&format!("{:<15}", format!("{}", status)));
-            for regional in &all_regionals {
-                let count = data.by_status_and_regional
-                    .get(&(status.clone(), regional.clone()))
-                    .map(|v| v.len())
-                    .unwrap_or(0);
-                if count > 0 {
-                    section.push_str(&format!("{:<10}", count));
+        StatusRegionalMatrix { statuses, regionals, counts }
+    }
+    
+    fn status_regional_matrix(data: &AggregatedData) -> Section {
+        let matrix = Self::matrix(data);
+        
+        let status = t("column.status");
+        let mut headers = vec![status.as_str()];
+        headers.extend(matrix.regionals.iter().map(String::as_str));
+        let mut table = Table::new(&headers);
+        
+        for (status, counts) in matrix.statuses.iter().zip(&matrix.counts) {
+            let mut row = vec![Span::plain(status)];
+            row.extend(counts.iter().map(|count| {
+                if *count > 0 {
+                    Span::plain(i18n::number(*count))
                 } else {
-                    section.push_str(&format!("{:<10}", "-"));
+                    Span::new("-", Tone::Muted)
                 }
-            }
-            section.push('\n');
+            }));
+            table.row(row);
         }
-        
-        section.push_str("\n\n");
-        section
+        Section::new("🔀", &t("summary.matrix")).with(Block::Table(table))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,285 @@
+//! Exportação tabular das discrepâncias, uma linha por discrepância, para
+//! as equipes das regionais trabalharem em planilha.
+
+use crate::{
+    error::{AppError, Result},
+    i18n::{t, tf},
+    models::common::{DataSource, EstacaoInfo},
+    services::comparator::{ComparisonResult, DiscrepancyCategory},
+    utils::choice::Choice,
+};
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Chaves do catálogo dos cabeçalhos, na ordem de `DiscrepancyRow::cells`
+const HEADER_KEYS: [&str; 10] = [
+    "column.category",
+    "column.station",
+    "column.zabbix_name",
+    "column.status_zabbix",
+    "column.regional_zabbix",
+    "column.status_sharepoint",
+    "column.holder",
+    "column.owner",
+    "column.pending",
+    "column.description",
+];
+
+/// Cabeçalhos no idioma atual
+fn headers() -> Vec<String> {
+    HEADER_KEYS.iter().map(|key| t(key)).collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscrepancyRow {
+    pub category: DiscrepancyCategory,
+    pub station: String,
+    pub zabbix_nome: String,
+    pub zabbix_status: String,
+    pub zabbix_regional: String,
+    pub sharepoint_status: String,
+    pub detentor: String,
+    pub responsavel: String,
+    pub pendencia: String,
+    pub description: String,
+}
+
+/// Colunas sem a categoria, para relatórios agrupados por categoria
+pub(crate) fn detail_headers() -> Vec<String> {
+    headers().split_off(1)
+}
+
+impl DiscrepancyRow {
+    /// Células na ordem de `detail_headers()`
+    pub(crate) fn detail_cells(&self) -> Vec<String> {
+        self.cells()[1..].to_vec()
+    }
+
+    fn cells(&self) -> [String; 10] {
+        [
+            self.category.to_string(),
+            self.station.clone(),
+            self.zabbix_nome.clone(),
+            self.zabbix_status.clone(),
+            self.zabbix_regional.clone(),
+            self.sharepoint_status.clone(),
+            self.detentor.clone(),
+            self.responsavel.clone(),
+            self.pendencia.clone(),
+            self.description.clone(),
+        ]
+    }
+}
+
+/// Registros de cada origem conhecidos para um rótulo de estação (nome
+/// no Zabbix, "ID de rede" ou chave normalizada)
+#[derive(Default)]
+struct StationIndex<'a> {
+    records: HashMap<&'a str, (Option<&'a EstacaoInfo>, Option<&'a EstacaoInfo>)>,
+}
+
+impl<'a> StationIndex<'a> {
+    fn new(comparison: &'a ComparisonResult) -> Self {
+        let mut index = Self::default();
+        for diff in &comparison.diffs {
+            index.pair(&[&diff.nome, &diff.id], diff.zabbix.as_ref(), diff.sharepoint.as_ref());
+        }
+        for (zabbix, sharepoint) in &comparison.matching {
+            index.pair(&[&zabbix.nome, &sharepoint.id], Some(zabbix), Some(sharepoint));
+        }
+        for probable in &comparison.probable_matches {
+            index.pair(&[&probable.zabbix.nome], Some(&probable.zabbix), Some(&probable.sharepoint));
+        }
+        for duplicate in &comparison.duplicates {
+            if let Some(first) = duplicate.records.first() {
+                index.add(&duplicate.key, first);
+            }
+        }
+        for estacao in comparison.only_in_zabbix.iter().chain(&comparison.only_in_sharepoint) {
+            index.add(&estacao.nome, estacao);
+            index.add(&estacao.id, estacao);
+        }
+        index
+    }
+
+    fn pair(&mut self, labels: &[&'a str], zabbix: Option<&'a EstacaoInfo>, sharepoint: Option<&'a EstacaoInfo>) {
+        for label in labels {
+            let entry = self.records.entry(label).or_default();
+            entry.0 = entry.0.or(zabbix);
+            entry.1 = entry.1.or(sharepoint);
+        }
+        if let Some(sharepoint) = sharepoint {
+            self.add(&sharepoint.id, sharepoint);
+        }
+    }
+
+    fn add(&mut self, label: &'a str, estacao: &'a EstacaoInfo) {
+        let entry = self.records.entry(label).or_default();
+        match estacao.source {
+            DataSource::Zabbix => entry.0 = entry.0.or(Some(estacao)),
+            DataSource::SharePoint => entry.1 = entry.1.or(Some(estacao)),
+        }
+    }
+
+    fn get(&self, label: &str) -> (Option<&'a EstacaoInfo>, Option<&'a EstacaoInfo>) {
+        self.records.get(label).copied().unwrap_or_default()
+    }
+}
+
+/// Formato do arquivo exportado, deduzido da extensão
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    Csv,
+    Xlsx,
+}
+
+impl TableFormat {
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => Ok(Self::Csv),
+            Some(ext) if ext.eq_ignore_ascii_case("xlsx") => Ok(Self::Xlsx),
+            _ => Err(AppError::Generic(tf(
+                "table.unsupported_extension",
+                &[("path", &path.display())],
+            ))),
+        }
+    }
+}
+
+pub struct TableReport;
+
+impl TableReport {
+    /// Uma linha por discrepância em aberto, na ordem de `discrepancies()`
+    pub fn rows(comparison: &ComparisonResult) -> Vec<DiscrepancyRow> {
+        let index = StationIndex::new(comparison);
+        let text = |value: Option<&String>| value.cloned().unwrap_or_default();
+
+        comparison
+            .discrepancies()
+            .into_iter()
+            .map(|discrepancy| {
+                let (zabbix, sharepoint) = index.get(&discrepancy.station);
+                DiscrepancyRow {
+                    category: discrepancy.category,
+                    station: discrepancy.station,
+                    zabbix_nome: text(zabbix.map(|z| &z.nome)),
+                    zabbix_status: zabbix.map(|z| z.status.to_string()).unwrap_or_default(),
+                    zabbix_regional: text(zabbix.and_then(|z| z.regional.as_ref()).map(|r| &r.0)),
+                    sharepoint_status: sharepoint.map(|s| s.status.to_string()).unwrap_or_default(),
+                    detentor: text(sharepoint.and_then(|s| s.detentor.as_ref())),
+                    responsavel: text(sharepoint.and_then(|s| s.responsavel.as_ref())),
+                    pendencia: text(sharepoint.and_then(|s| s.pendencia.as_ref())),
+                    description: discrepancy.description,
+                }
+            })
+            .collect()
+    }
+
+    /// Grava as linhas no formato indicado pela extensão do arquivo
+    pub fn export(comparison: &ComparisonResult, path: &Path) -> Result<()> {
+        let rows = Self::rows(comparison);
+        match TableFormat::from_path(path)? {
+            TableFormat::Csv => std::fs::write(path, Self::to_csv(&rows)?)?,
+            TableFormat::Xlsx => Self::write_xlsx(&rows, path)
+                .map_err(|e| AppError::Generic(tf("table.write_failed", &[("path", &path.display()), ("error", &e)])))?,
+        }
+        Ok(())
+    }
+
+    pub fn to_csv(rows: &[DiscrepancyRow]) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(headers())?;
+        for row in rows {
+            writer.write_record(row.cells())?;
+        }
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| AppError::Generic(e.to_string()))?;
+        Ok(String::from_utf8_lossy(&bytes).to_string())
+    }
+
+    /// Uma planilha por categoria com discrepâncias, com filtro e
+    /// cabeçalho congelado
+    pub fn write_xlsx(rows: &[DiscrepancyRow], path: &Path) -> std::result::Result<(), XlsxError> {
+        let mut workbook = Workbook::new();
+        let header_format = Format::new().set_bold();
+        let headers = headers();
+
+        for category in DiscrepancyCategory::ALL {
+            let category_rows: Vec<_> = rows.iter().filter(|r| r.category == *category).collect();
+            if category_rows.is_empty() {
+                continue;
+            }
+
+            let sheet = workbook.add_worksheet();
+            sheet.set_name(category.to_string())?;
+            for (col, header) in headers.iter().enumerate() {
+                sheet.write_string_with_format(0, col as u16, header, &header_format)?;
+            }
+            for (i, row) in category_rows.iter().enumerate() {
+                for (col, cell) in row.cells().iter().enumerate() {
+                    sheet.write_string(i as u32 + 1, col as u16, cell)?;
+                }
+            }
+            sheet.autofilter(0, 0, category_rows.len() as u32, headers.len() as u16 - 1)?;
+            sheet.set_freeze_panes(1, 0)?;
+            sheet.autofit();
+        }
+
+        // Uma pasta de trabalho precisa de ao menos uma planilha
+        if rows.is_empty() {
+            let sheet = workbook.add_worksheet();
+            for (col, header) in headers.iter().enumerate() {
+                sheet.write_string_with_format(0, col as u16, header, &header_format)?;
+            }
+        }
+
+        workbook.save(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::common::{estacao, Status};
+    use crate::services::comparator::Comparator;
+
+    #[test]
+    fn test_rows_with_both_sources() {
+        let mut sp = estacao("RFeye002300", Status::Ativo, Some("GR01"), DataSource::SharePoint);
+        sp.detentor = Some("GR01 Anatel".to_string());
+        sp.responsavel = Some("Fulano".to_string());
+        sp.pendencia = Some("Trocar antena".to_string());
+        let comparison = Comparator::compare(
+            vec![
+                estacao("RFeye002300", Status::Defeito, Some("GR01"), DataSource::Zabbix),
+                estacao("RFeye002301", Status::Ativo, Some("GR01"), DataSource::Zabbix),
+            ],
+            vec![sp],
+            &Config::default(),
+        );
+
+        let rows = TableReport::rows(&comparison);
+        let status = rows.iter().find(|r| r.category == DiscrepancyCategory::Status).unwrap();
+        assert_eq!(status.zabbix_status, "Defeito");
+        assert_eq!(status.zabbix_regional, "GR01");
+        assert_eq!(status.sharepoint_status, "Ativo");
+        assert_eq!(status.responsavel, "Fulano");
+        assert_eq!(status.pendencia, "Trocar antena");
+
+        let only = rows.iter().find(|r| r.category == DiscrepancyCategory::OnlyInZabbix).unwrap();
+        assert_eq!(only.zabbix_nome, "RFeye002301");
+        assert_eq!(only.detentor, "");
+
+        let csv = TableReport::to_csv(&rows).unwrap();
+        assert!(csv.starts_with("Categoria,Estação,Nome Zabbix,"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("discrepancias.xlsx");
+        TableReport::export(&comparison, &path).unwrap();
+        assert!(path.metadata().unwrap().len() > 0);
+        assert!(TableFormat::from_path(Path::new("x.pdf")).is_err());
+    }
+}
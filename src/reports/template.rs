@@ -0,0 +1,90 @@
+//! Relatórios com layout próprio, em templates Jinja (minijinja).
+//!
+//! O template recebe `report` (o modelo neutro: `title`, `subtitle`,
+//! `sections`, `footer`) e `data` (os dados brutos do comando, no mesmo
+//! formato do JSON). Arquivos `.html` e `.xml` têm escape automático.
+
+use crate::{
+    error::{AppError, Result},
+    reports::{model::Report, render::ReportRenderer},
+};
+use minijinja::{context, Environment};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+pub struct TemplateRenderer {
+    name: String,
+    source: String,
+    data: serde_json::Value,
+}
+
+impl TemplateRenderer {
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let source = fs::read_to_string(path)
+            .map_err(|e| AppError::Template(format!("{}: {}", path.display(), e)))?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "template".to_string());
+        Ok(Self::new(&name, source))
+    }
+
+    /// `name` define o escape automático pela extensão
+    pub fn new(name: &str, source: String) -> Self {
+        Self {
+            name: name.to_string(),
+            source,
+            data: serde_json::Value::Null,
+        }
+    }
+
+    /// Dados brutos expostos como `data`
+    pub fn with_data(mut self, data: &impl Serialize) -> Result<Self> {
+        self.data = serde_json::to_value(data)?;
+        Ok(self)
+    }
+}
+
+impl ReportRenderer for TemplateRenderer {
+    fn render(&self, report: &Report) -> Result<String> {
+        let mut env = Environment::new();
+        env.add_template(&self.name, &self.source)
+            .map_err(|e| AppError::Template(format!("{}: {}", self.name, e)))?;
+        env.get_template(&self.name)
+            .and_then(|template| template.render(context! { report => report, data => &self.data }))
+            .map_err(|e| AppError::Template(format!("{}: {}", self.name, e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reports::model::{Block, Section, Span, Table, Tone};
+
+    #[test]
+    fn test_template_sees_report_and_data() {
+        let mut table = Table::new(&["Estação"]);
+        table.row(vec![Span::new("<RFeye002300>", Tone::Key)]);
+        let mut report = Report::new("Relatório");
+        report.push(Section::new("📡", "Apenas no Zabbix").with(Block::Table(table)));
+
+        let source = "{{ report.title }}: {% for s in report.sections %}{{ s.title }} \
+                      {{ s.blocks[0].rows[0][0].text }}{% endfor %} ({{ data.total }})"
+            .to_string();
+        let data = serde_json::json!({ "total": 7 });
+
+        let text = TemplateRenderer::new("relatorio.txt", source.clone())
+            .with_data(&data)
+            .unwrap()
+            .render(&report)
+            .unwrap();
+        assert_eq!(text, "Relatório: Apenas no Zabbix <RFeye002300> (7)");
+
+        let html = TemplateRenderer::new("relatorio.html", source).render(&report).unwrap();
+        assert!(html.contains("&lt;RFeye002300&gt;"));
+
+        let broken = TemplateRenderer::new("x.txt", "{% for %}".to_string()).render(&report);
+        assert!(matches!(broken, Err(AppError::Template(_))));
+    }
+}
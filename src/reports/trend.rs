@@ -0,0 +1,72 @@
+use crate::{
+    i18n::{self, t, tf},
+    reports::{
+        model::{Block, Report, Section, Span, Table, Tone},
+        render::TextRenderer,
+    },
+    services::trend::{Trend, ALL_REGIONALS},
+};
+
+pub struct TrendReport;
+
+impl TrendReport {
+    pub fn generate(trend: &Trend) -> String {
+        TextRenderer::render_text(&Self::model(trend))
+    }
+
+    /// Relatório independente de formato, para qualquer `ReportRenderer`
+    pub fn model(trend: &Trend) -> Report {
+        let mut report = Report::new(&t("trend.title"));
+        if let (Some(first), Some(last)) = (trend.dates.first(), trend.dates.last()) {
+            report = report.with_subtitle(&tf(
+                "trend.range",
+                &[
+                    ("from", &i18n::date(first.date_naive())),
+                    ("to", &i18n::date(last.date_naive())),
+                    ("count", &i18n::number(trend.dates.len())),
+                ],
+            ));
+        }
+
+        report.push(Self::series_section(trend));
+        report
+    }
+
+    fn series_section(trend: &Trend) -> Section {
+        let mut table = Table::new(&[
+            &t("column.status"),
+            &t("column.regional"),
+            &t("column.series"),
+            &t("column.latest"),
+            "Δ",
+            &t("column.change"),
+        ]);
+
+        for series in &trend.series {
+            let tone = if series.regional == ALL_REGIONALS { Tone::Strong } else { Tone::Plain };
+            table.row(vec![
+                Span::plain(&series.status),
+                Span::new(&series.regional, tone),
+                Span::plain(Self::tail(&series.sparkline(), 24)),
+                Span::plain(series.latest()),
+                Span::plain(Self::signed(series.delta())),
+                Span::plain(Self::signed(series.change())),
+            ]);
+        }
+        Section::new("📈", &t("trend.series")).with(Block::Table(table))
+    }
+
+    /// Últimos `width` pontos, para caber na coluna
+    fn tail(sparkline: &str, width: usize) -> String {
+        let chars: Vec<char> = sparkline.chars().collect();
+        chars[chars.len().saturating_sub(width)..].iter().collect()
+    }
+
+    fn signed(value: i64) -> String {
+        match value {
+            0 => "0".to_string(),
+            v if v > 0 => format!("+{}", v),
+            v => v.to_string(),
+        }
+    }
+}
@@ -1,18 +1,22 @@
 use crate::models::common::{EstacaoInfo, Status};
 use indexmap::IndexMap;
+use serde::{ser::SerializeSeq, Serialize, Serializer};
 use std::collections::HashMap;
 
 pub struct Aggregator;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AggregatedData {
     pub by_status: HashMap<Status, Vec<EstacaoInfo>>,
     pub by_regional: HashMap<String, Vec<EstacaoInfo>>,
+    /// Em JSON, lista de `{status, regional, estacoes}` (chaves compostas
+    /// não viram chaves de objeto)
+    #[serde(serialize_with = "serialize_groups")]
     pub by_status_and_regional: IndexMap<(Status, String), Vec<EstacaoInfo>>,
     pub totals: AggregationTotals,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AggregationTotals {
     pub total: usize,
     pub by_status: HashMap<Status, usize>,
@@ -20,6 +24,24 @@ pub struct AggregationTotals {
     pub without_regional: usize,
 }
 
+#[derive(Serialize)]
+struct Group<'a> {
+    status: &'a Status,
+    regional: &'a str,
+    estacoes: &'a [EstacaoInfo],
+}
+
+fn serialize_groups<S: Serializer>(
+    groups: &IndexMap<(Status, String), Vec<EstacaoInfo>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut seq = serializer.serialize_seq(Some(groups.len()))?;
+    for ((status, regional), estacoes) in groups {
+        seq.serialize_element(&Group { status, regional, estacoes })?;
+    }
+    seq.end()
+}
+
 impl Aggregator {
     pub fn group_by_status_and_regional(estacoes: &[EstacaoInfo]) -> AggregatedData {
         let mut by_status: HashMap<Status, Vec<EstacaoInfo>> = HashMap::new();
@@ -35,7 +57,7 @@ impl Aggregator {
             // Agrupar por status
             by_status
                 .entry(estacao.status.clone())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(estacao.clone());
             
             *status_counts.entry(estacao.status.clone()).or_insert(0) += 1;
@@ -44,7 +66,7 @@ impl Aggregator {
             if let Some(regional) = &estacao.regional {
                 by_regional
                     .entry(regional.0.clone())
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(estacao.clone());
                 
                 *regional_counts.entry(regional.0.clone()).or_insert(0) += 1;
@@ -52,7 +74,7 @@ impl Aggregator {
                 // Agrupar por status e regional
                 by_status_and_regional
                     .entry((estacao.status.clone(), regional.0.clone()))
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(estacao.clone());
             } else {
                 without_regional += 1;
@@ -60,12 +82,12 @@ impl Aggregator {
                 // Adicionar ao grupo "SEM_REGIONAL"
                 by_regional
                     .entry("SEM_REGIONAL".to_string())
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(estacao.clone());
                 
                 by_status_and_regional
                     .entry((estacao.status.clone(), "SEM_REGIONAL".to_string()))
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(estacao.clone());
             }
         }
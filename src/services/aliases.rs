@@ -0,0 +1,188 @@
+//! Arquivo de apelidos: associa um host do Zabbix (hostid ou nome) ao
+//! "ID de rede" do SharePoint quando os nomes nunca vão coincidir após a
+//! normalização.
+//!
+//! ```toml
+//! [[alias]]
+//! zabbix = "10105"            # hostid ou nome do host
+//! sharepoint = "RFeye002310"
+//! note = "renomeado em 2023"
+//! ```
+
+use crate::{
+    error::{AppError, Result},
+    i18n::tf,
+    models::common::EstacaoInfo,
+};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Aliases {
+    #[serde(default, rename = "alias")]
+    pub entries: Vec<Alias>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Alias {
+    /// hostid ou nome do host no Zabbix
+    pub zabbix: String,
+    /// "ID de rede" no SharePoint
+    pub sharepoint: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl Aliases {
+    /// Lê o arquivo; um arquivo inexistente equivale a nenhum apelido
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| AppError::Config(format!("{}: {}", path.display(), e)))?;
+        Self::from_toml_str(&content)
+            .map_err(|e| AppError::Config(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        toml::from_str(content).map_err(|e| AppError::Config(e.to_string()))
+    }
+
+    /// Acrescenta um apelido ao fim do arquivo, preservando o conteúdo
+    /// (e os comentários) existentes
+    pub fn append(path: &Path, alias: &Alias) -> Result<()> {
+        let existing = Self::load(path)?;
+        if let Some(current) = existing.find(&alias.zabbix) {
+            return Err(AppError::Config(tf(
+                "error.alias_taken",
+                &[("zabbix", &alias.zabbix), ("sharepoint", &current.sharepoint)],
+            )));
+        }
+
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+
+        let block = toml::to_string(&Aliases {
+            entries: vec![alias.clone()],
+        })
+        .map_err(|e| AppError::Config(e.to_string()))?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let separator = if existing.entries.is_empty() && fs::metadata(path)?.len() == 0 {
+            ""
+        } else {
+            "\n"
+        };
+        write!(file, "{}{}", separator, block)?;
+        Ok(())
+    }
+
+    fn find(&self, zabbix: &str) -> Option<&Alias> {
+        self.entries
+            .iter()
+            .find(|alias| alias.zabbix.eq_ignore_ascii_case(zabbix))
+    }
+
+    /// "ID de rede" associado a um host do Zabbix; um apelido pelo hostid
+    /// vale mais que um pelo nome, qualquer que seja a ordem no arquivo
+    pub fn sharepoint_id(&self, zabbix: &EstacaoInfo) -> Option<&str> {
+        let by_hostid = zabbix
+            .zabbix_hostid
+            .as_deref()
+            .and_then(|hostid| self.entries.iter().find(|alias| alias.zabbix == hostid));
+        by_hostid
+            .or_else(|| self.find(&zabbix.nome))
+            .map(|alias| alias.sharepoint.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::common::{estacao, DataSource, Status};
+
+    fn alias(zabbix: &str, sharepoint: &str) -> Alias {
+        Alias {
+            zabbix: zabbix.to_string(),
+            sharepoint: sharepoint.to_string(),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_append_keeps_existing_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aliases.toml");
+        fs::write(&path, "# apelidos confirmados\n").unwrap();
+
+        let alias = Alias {
+            zabbix: "10105".to_string(),
+            sharepoint: "RFeye002310".to_string(),
+            note: None,
+        };
+        Aliases::append(&path, &alias).unwrap();
+        Aliases::append(
+            &path,
+            &Alias {
+                zabbix: "RFeye-antigo".to_string(),
+                sharepoint: "RFeye002311".to_string(),
+                note: Some("renomeado".to_string()),
+            },
+        )
+        .unwrap();
+        assert!(Aliases::append(&path, &alias).is_err());
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# apelidos confirmados"));
+        let aliases = Aliases::load(&path).unwrap();
+        assert_eq!(aliases.entries.len(), 2);
+        assert_eq!(aliases.entries[1].note.as_deref(), Some("renomeado"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("novo").join("aliases.toml");
+        assert!(Aliases::load(&path).unwrap().entries.is_empty());
+
+        let entries = vec![
+            alias("10105", "RFeye002310"),
+            Alias {
+                note: Some("renomeado em 2023, \"antigo\"".to_string()),
+                ..alias("RFeye-antigo", "RFeye002311")
+            },
+        ];
+        for entry in &entries {
+            Aliases::append(&path, entry).unwrap();
+        }
+
+        assert_eq!(Aliases::load(&path).unwrap().entries, entries);
+        // O arquivo gravado é o mesmo formato documentado no módulo
+        let parsed = Aliases::from_toml_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(parsed.entries, entries);
+    }
+
+    #[test]
+    fn test_hostid_takes_precedence_over_name() {
+        let mut host = estacao("RFeye-antigo", Status::Ativo, None, DataSource::Zabbix);
+        host.zabbix_hostid = Some("10105".to_string());
+
+        // O apelido pelo nome vem primeiro no arquivo, mas o hostid decide
+        let aliases = Aliases {
+            entries: vec![alias("rfeye-antigo", "RFeye002311"), alias("10105", "RFeye002310")],
+        };
+        assert_eq!(aliases.sharepoint_id(&host), Some("RFeye002310"));
+
+        // Sem apelido para o hostid, vale o nome, sem diferenciar maiúsculas
+        host.zabbix_hostid = Some("10999".to_string());
+        assert_eq!(aliases.sharepoint_id(&host), Some("RFeye002311"));
+
+        // Nem o hostid nem o nome têm apelido
+        let other = estacao("RFeye002399", Status::Ativo, None, DataSource::Zabbix);
+        assert_eq!(aliases.sharepoint_id(&other), None);
+    }
+}
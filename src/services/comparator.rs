@@ -1,26 +1,328 @@
-use crate::models::common::{EstacaoInfo, Status};
+use crate::config::Config;
+use crate::i18n::{percent, t, tf};
+use crate::models::common::{DataSource, EstacaoInfo, Regional, Status, TipoEstacao};
+use crate::models::compare::{ComparableField, DuplicatePolicy, StatusRule};
+use crate::parsers::diagnostic::ParseDiagnostic;
+use crate::services::aliases::Aliases;
+use crate::services::waivers::{AcceptedItem, Waiver};
+use crate::utils::choice::Choice;
 use crate::utils::normalizer::Normalizer;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::net::IpAddr;
+
+/// Valor exibido para o lado que não informa um campo divergente
+pub const MISSING: &str = "—";
 
 pub struct Comparator;
 
 impl Comparator {
+    /// Compara os campos selecionados de um par de estações. Um campo só
+    /// diverge quando as duas origens o informam, exceto a regional, que
+    /// diverge também quando só uma origem a informa (o lado ausente vale
+    /// `MISSING`); status diferentes cobertos por uma regra de equivalência
+    /// não divergem.
+    pub fn diff_fields(
+        zabbix: &EstacaoInfo,
+        sharepoint: &EstacaoInfo,
+        fields: &[ComparableField],
+        status_rules: &[StatusRule],
+    ) -> Vec<FieldDiff> {
+        fields
+            .iter()
+            .filter(|field| {
+                **field != ComparableField::Status
+                    || !status_rules.iter().any(|rule| rule.matches(Some(zabbix), sharepoint))
+            })
+            .filter_map(|field| {
+                let (zabbix_value, sharepoint_value) = match (field.value(zabbix), field.value(sharepoint)) {
+                    (Some(z), Some(sp)) => (z, sp),
+                    (None, None) => return None,
+                    (z, sp) if *field == ComparableField::Regional => (
+                        z.unwrap_or_else(|| MISSING.to_string()),
+                        sp.unwrap_or_else(|| MISSING.to_string()),
+                    ),
+                    _ => return None,
+                };
+                (field_key(*field, &zabbix_value) != field_key(*field, &sharepoint_value)).then_some(FieldDiff {
+                    field: *field,
+                    zabbix_value,
+                    sharepoint_value,
+                })
+            })
+            .collect()
+    }
+    
+    /// IPs inválidos e IPs repetidos entre estações de uma mesma origem
+    fn check_ips(estacoes: &[EstacaoInfo]) -> Vec<IpIssue> {
+        let mut issues = Vec::new();
+        let mut by_ip: HashMap<IpAddr, Vec<&EstacaoInfo>> = HashMap::new();
+        
+        for estacao in estacoes {
+            let Some(ip) = &estacao.ip else { continue };
+            match parse_ip(ip) {
+                Some(addr) => by_ip.entry(addr).or_default().push(estacao),
+                None => issues.push(IpIssue {
+                    id: estacao.id.clone(),
+                    nome: estacao.nome.clone(),
+                    source: estacao.source,
+                    ip: Some(ip.clone()),
+                    kind: IpIssueKind::Invalid,
+                }),
+            }
+        }
+        
+        for group in by_ip.values().filter(|group| group.len() > 1) {
+            for estacao in group {
+                let others = group
+                    .iter()
+                    .filter(|other| other.id != estacao.id)
+                    .map(|other| other.nome.clone())
+                    .collect();
+                issues.push(IpIssue {
+                    id: estacao.id.clone(),
+                    nome: estacao.nome.clone(),
+                    source: estacao.source,
+                    ip: estacao.ip.clone(),
+                    kind: IpIssueKind::Duplicate(others),
+                });
+            }
+        }
+        
+        issues
+    }
+    
+    /// Confiança de que duas estações sem correspondência exata sejam a
+    /// mesma: 60% pela similaridade dos nomes, 30% pelo número de série e
+    /// 10% pelo tipo. Séries ou tipos conhecidos e diferentes descartam o par.
+    pub fn match_confidence(zabbix: &EstacaoInfo, sharepoint: &EstacaoInfo) -> Option<f64> {
+        let same_serial = match (Normalizer::serial(&zabbix.nome), Normalizer::serial(&sharepoint.id)) {
+            (Some(z), Some(sp)) if z != sp => return None,
+            (Some(_), Some(_)) => true,
+            _ => false,
+        };
+        let same_tipo = match (&zabbix.tipo, &sharepoint.tipo) {
+            (TipoEstacao::Outro(_), _) | (_, TipoEstacao::Outro(_)) => false,
+            (z, sp) if z != sp => return None,
+            _ => true,
+        };
+        
+        let similarity = Normalizer::similarity(&zabbix.nome, &sharepoint.id);
+        let confidence = 0.6 * similarity
+            + if same_serial { 0.3 } else { 0.0 }
+            + if same_tipo { 0.1 } else { 0.0 };
+        Some(confidence)
+    }
+    
+    /// Associa as estações que sobraram pela maior confiança, retirando
+    /// os pares das listas de exclusivas
+    fn probable_matches(
+        only_in_zabbix: &mut Vec<EstacaoInfo>,
+        only_in_sharepoint: &mut Vec<EstacaoInfo>,
+        min_confidence: f64,
+    ) -> Vec<ProbableMatch> {
+        let mut candidates = Vec::new();
+        for (zi, zabbix) in only_in_zabbix.iter().enumerate() {
+            for (si, sharepoint) in only_in_sharepoint.iter().enumerate() {
+                if let Some(confidence) = Self::match_confidence(zabbix, sharepoint) {
+                    if confidence >= min_confidence {
+                        candidates.push((confidence, zi, si));
+                    }
+                }
+            }
+        }
+        candidates.sort_by(|a, b| {
+            b.0.total_cmp(&a.0)
+                .then_with(|| only_in_zabbix[a.1].nome.cmp(&only_in_zabbix[b.1].nome))
+                .then_with(|| only_in_sharepoint[a.2].id.cmp(&only_in_sharepoint[b.2].id))
+        });
+        
+        let mut used_zabbix = HashSet::new();
+        let mut used_sharepoint = HashSet::new();
+        let mut chosen = Vec::new();
+        for (confidence, zi, si) in candidates {
+            if used_zabbix.contains(&zi) || used_sharepoint.contains(&si) {
+                continue;
+            }
+            used_zabbix.insert(zi);
+            used_sharepoint.insert(si);
+            chosen.push((confidence, zi, si));
+        }
+        
+        let matches = chosen
+            .into_iter()
+            .map(|(confidence, zi, si)| ProbableMatch {
+                zabbix: only_in_zabbix[zi].clone(),
+                sharepoint: only_in_sharepoint[si].clone(),
+                confidence,
+            })
+            .collect();
+        
+        *only_in_zabbix = Self::without(std::mem::take(only_in_zabbix), &used_zabbix);
+        *only_in_sharepoint = Self::without(std::mem::take(only_in_sharepoint), &used_sharepoint);
+        
+        matches
+    }
+    
+    fn without(estacoes: Vec<EstacaoInfo>, removed: &HashSet<usize>) -> Vec<EstacaoInfo> {
+        estacoes
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| !removed.contains(i))
+            .map(|(_, estacao)| estacao)
+            .collect()
+    }
+    
+    /// Agrupa os registros de uma origem que têm a mesma chave normalizada
+    /// e mantém, de cada grupo, o registro escolhido pela política
+    fn resolve_duplicates(
+        estacoes: Vec<EstacaoInfo>,
+        key: impl Fn(&EstacaoInfo) -> String,
+        policy: DuplicatePolicy,
+    ) -> (Vec<EstacaoInfo>, Vec<Duplicate>) {
+        let mut groups: IndexMap<String, Vec<EstacaoInfo>> = IndexMap::new();
+        for estacao in estacoes {
+            groups.entry(key(&estacao)).or_default().push(estacao);
+        }
+        
+        let mut kept = Vec::new();
+        let mut duplicates = Vec::new();
+        for (key, mut records) in groups {
+            if records.len() == 1 {
+                kept.extend(records);
+                continue;
+            }
+            
+            let winner = match policy {
+                DuplicatePolicy::First => records.first().cloned(),
+                DuplicatePolicy::Last => records.last().cloned(),
+                DuplicatePolicy::Skip => None,
+            };
+            kept.extend(winner);
+            
+            records.sort_by(|a, b| a.id.cmp(&b.id));
+            duplicates.push(Duplicate {
+                key,
+                source: records[0].source,
+                records,
+            });
+        }
+        
+        (kept, duplicates)
+    }
+    
     pub fn compare(
         zabbix_data: Vec<EstacaoInfo>,
         sharepoint_data: Vec<EstacaoInfo>,
+        config: &Config,
+    ) -> ComparisonResult {
+        Self::compare_with_aliases(zabbix_data, sharepoint_data, config, &Aliases::default())
+    }
+    
+    /// Chave de correspondência de um host do Zabbix: o "ID de rede" do
+    /// apelido ou o nome, normalizados
+    pub fn zabbix_key(estacao: &EstacaoInfo, config: &Config, aliases: &Aliases) -> String {
+        let name = aliases.sharepoint_id(estacao).unwrap_or(&estacao.nome);
+        Normalizer::normalize_id(name, config)
+    }
+    
+    /// Como `compare`, mas os hosts com apelido usam o "ID de rede"
+    /// associado no lugar do nome
+    pub fn compare_with_aliases(
+        zabbix_data: Vec<EstacaoInfo>,
+        sharepoint_data: Vec<EstacaoInfo>,
+        config: &Config,
+        aliases: &Aliases,
     ) -> ComparisonResult {
+        let zabbix_key = |estacao: &EstacaoInfo| Self::zabbix_key(estacao, config, aliases);
+        
+        let mut ip_issues = Self::check_ips(&zabbix_data);
+        ip_issues.extend(Self::check_ips(&sharepoint_data));
+        
+        let policy = config.compare.duplicate_policy;
+        let (zabbix_data, mut duplicates) = Self::resolve_duplicates(
+            zabbix_data,
+            zabbix_key,
+            policy,
+        );
+        let (sharepoint_data, sharepoint_duplicates) = Self::resolve_duplicates(
+            sharepoint_data,
+            |estacao| Normalizer::normalize_id(&estacao.id, config),
+            policy,
+        );
+        duplicates.extend(sharepoint_duplicates);
+        
+        let mut zabbix_slots: Vec<Option<EstacaoInfo>> = zabbix_data.into_iter().map(Some).collect();
+        let zabbix_by_hostid: HashMap<String, usize> = zabbix_slots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| Some((e.as_ref()?.zabbix_hostid.clone()?, i)))
+            .collect();
+        
+        // O hostid do "Link Zabbix" é a chave mais forte; a correspondência
+        // por nome normalizado só vale para o que sobrar
+        let mut pairs: Vec<(String, EstacaoInfo, EstacaoInfo)> = Vec::new();
+        let mut broken_links = Vec::new();
+        let mut sharepoint_rest = Vec::new();
+        // "ID de rede" do registro que ficou com cada host
+        let mut linked_by: HashMap<usize, String> = HashMap::new();
+        
+        for estacao in sharepoint_data {
+            let key = Normalizer::normalize_id(&estacao.id, config);
+            let Some(hostid) = estacao.zabbix_hostid.clone() else {
+                sharepoint_rest.push(estacao);
+                continue;
+            };
+            
+            match zabbix_by_hostid.get(&hostid) {
+                Some(&index) => match zabbix_slots[index].take() {
+                    Some(zabbix) => {
+                        if zabbix_key(&zabbix) != key {
+                            broken_links.push(BrokenLink {
+                                id: estacao.id.clone(),
+                                hostid,
+                                kind: BrokenLinkKind::NameMismatch(zabbix.nome.clone()),
+                            });
+                        }
+                        linked_by.insert(index, estacao.id.clone());
+                        pairs.push((key, zabbix, estacao));
+                    }
+                    // Host já associado a outro registro: o link é
+                    // reportado e o registro tenta a correspondência por nome
+                    None => {
+                        broken_links.push(BrokenLink {
+                            id: estacao.id.clone(),
+                            hostid,
+                            kind: BrokenLinkKind::AlreadyLinked(linked_by[&index].clone()),
+                        });
+                        sharepoint_rest.push(estacao);
+                    }
+                },
+                None => {
+                    broken_links.push(BrokenLink {
+                        id: estacao.id.clone(),
+                        hostid,
+                        kind: BrokenLinkKind::HostNotFound,
+                    });
+                    sharepoint_rest.push(estacao);
+                }
+            }
+        }
+        
         let mut zabbix_map: HashMap<String, EstacaoInfo> = HashMap::new();
         let mut sharepoint_map: HashMap<String, EstacaoInfo> = HashMap::new();
         
         // Normalizar e mapear dados do Zabbix
-        for estacao in zabbix_data {
-            let normalized_id = Normalizer::normalize_id(&estacao.nome);
+        for estacao in zabbix_slots.into_iter().flatten() {
+            let normalized_id = zabbix_key(&estacao);
             zabbix_map.insert(normalized_id, estacao);
         }
         
         // Normalizar e mapear dados do SharePoint
-        for estacao in sharepoint_data {
-            let normalized_id = Normalizer::normalize_id(&estacao.id);
+        for estacao in sharepoint_rest {
+            let normalized_id = Normalizer::normalize_id(&estacao.id, config);
             sharepoint_map.insert(normalized_id, estacao);
         }
         
@@ -28,21 +330,37 @@ impl Comparator {
         let sharepoint_keys: HashSet<_> = sharepoint_map.keys().cloned().collect();
         
         // Encontrar correspondências
-        let common_keys: HashSet<_> = zabbix_keys
-            .intersection(&sharepoint_keys)
-            .cloned()
-            .collect();
+        for key in zabbix_keys.intersection(&sharepoint_keys) {
+            pairs.push((key.clone(), zabbix_map[key].clone(), sharepoint_map[key].clone()));
+        }
         
         let mut matching = Vec::new();
         let mut status_mismatch = Vec::new();
+        let mut regional_mismatch = Vec::new();
+        let mut diffs = Vec::new();
         
-        for key in &common_keys {
-            let zabbix_estacao = &zabbix_map[key];
-            let sp_estacao = &sharepoint_map[key];
+        for (key, zabbix_estacao, sp_estacao) in pairs {
+            // IP informado só de um lado
+            match (&zabbix_estacao.ip, &sp_estacao.ip) {
+                (Some(_), None) => ip_issues.push(IpIssue::empty(&sp_estacao)),
+                (None, Some(_)) => ip_issues.push(IpIssue::empty(&zabbix_estacao)),
+                _ => {}
+            }
+            
+            let fields = Self::diff_fields(
+                &zabbix_estacao,
+                &sp_estacao,
+                &config.compare.fields,
+                &config.compare.status_rules,
+            );
+            if fields.is_empty() {
+                matching.push((zabbix_estacao, sp_estacao));
+                continue;
+            }
+            
+            let has_diff = |field: ComparableField| fields.iter().any(|d| d.field == field);
             
-            if zabbix_estacao.status == sp_estacao.status {
-                matching.push((zabbix_estacao.clone(), sp_estacao.clone()));
-            } else {
+            if has_diff(ComparableField::Status) {
                 status_mismatch.push(StatusMismatch {
                     id: key.clone(),
                     nome: zabbix_estacao.nome.clone(),
@@ -52,8 +370,31 @@ impl Comparator {
                     sharepoint_regional: sp_estacao.regional.clone(),
                 });
             }
+            
+            if has_diff(ComparableField::Regional) {
+                regional_mismatch.push(RegionalMismatch {
+                    id: key.clone(),
+                    nome: zabbix_estacao.nome.clone(),
+                    zabbix_regional: zabbix_estacao.regional.clone(),
+                    sharepoint_regional: sp_estacao.regional.clone(),
+                    zabbix_status: zabbix_estacao.status.clone(),
+                    sharepoint_status: sp_estacao.status.clone(),
+                });
+            }
+            
+            diffs.push(StationDiff {
+                id: key,
+                nome: zabbix_estacao.nome.clone(),
+                fields,
+                zabbix: Some(zabbix_estacao),
+                sharepoint: Some(sp_estacao),
+            });
         }
         
+        diffs.sort_by(|a, b| a.id.cmp(&b.id));
+        broken_links.sort_by(|a, b| a.id.cmp(&b.id));
+        ip_issues.sort_by(|a, b| a.id.cmp(&b.id));
+        
         // Encontrar estações únicas
         let only_zabbix_keys: HashSet<_> = zabbix_keys
             .difference(&sharepoint_keys)
@@ -64,37 +405,706 @@ impl Comparator {
             .cloned()
             .collect();
         
-        let only_in_zabbix: Vec<_> = only_zabbix_keys
+        let mut only_in_zabbix: Vec<_> = only_zabbix_keys
             .iter()
             .map(|k| zabbix_map[k].clone())
             .collect();
         
-        let only_in_sharepoint: Vec<_> = only_sharepoint_keys
+        // Registros cujo status já prevê a ausência no Zabbix (ex.: baixa)
+        let (expected_absent, mut only_in_sharepoint): (Vec<_>, Vec<_>) = only_sharepoint_keys
             .iter()
             .map(|k| sharepoint_map[k].clone())
-            .collect();
+            .partition(|estacao| {
+                config
+                    .compare
+                    .status_rules
+                    .iter()
+                    .any(|rule| rule.matches(None, estacao))
+            });
+        
+        let probable_matches = Self::probable_matches(
+            &mut only_in_zabbix,
+            &mut only_in_sharepoint,
+            config.compare.min_confidence,
+        );
         
         ComparisonResult {
             matching,
             only_in_zabbix,
             only_in_sharepoint,
+            expected_absent,
+            probable_matches,
+            duplicates,
             status_mismatch,
+            regional_mismatch,
+            diffs,
+            broken_links,
+            ip_issues,
+            diagnostics: Vec::new(),
+            unattributed_diagnostics: Vec::new(),
+            accepted: Vec::new(),
+            expired_waivers: Vec::new(),
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComparisonResult {
     pub matching: Vec<(EstacaoInfo, EstacaoInfo)>,
     pub only_in_zabbix: Vec<EstacaoInfo>,
     pub only_in_sharepoint: Vec<EstacaoInfo>,
+    /// Registros do SharePoint ausentes do Zabbix como previsto por uma
+    /// regra de status
+    pub expected_absent: Vec<EstacaoInfo>,
+    /// Pares sem correspondência exata que provavelmente são a mesma
+    /// estação, para confirmação manual
+    pub probable_matches: Vec<ProbableMatch>,
+    /// Registros de uma mesma origem que colidem na mesma chave
+    pub duplicates: Vec<Duplicate>,
     pub status_mismatch: Vec<StatusMismatch>,
+    pub regional_mismatch: Vec<RegionalMismatch>,
+    /// Todas as divergências de cada estação correspondente
+    pub diffs: Vec<StationDiff>,
+    /// "Link Zabbix" que não aponta para o host da estação
+    pub broken_links: Vec<BrokenLink>,
+    /// IPs vazios, inválidos ou repetidos
+    pub ip_issues: Vec<IpIssue>,
+    /// Erros de leitura registrados no modo tolerante
+    pub diagnostics: Vec<ParseDiagnostic>,
+    /// Erros de leitura de linhas que o recorte não consegue atribuir a
+    /// uma estação; vazio sem recorte
+    #[serde(default)]
+    pub unattributed_diagnostics: Vec<ParseDiagnostic>,
+    /// Discrepâncias cobertas por uma isenção válida
+    pub accepted: Vec<AcceptedItem>,
+    /// Isenções vencidas que ainda cobririam alguma discrepância
+    pub expired_waivers: Vec<Waiver>,
+}
+
+/// Uma discrepância, independente da categoria. Categoria, estação e
+/// campo a identificam entre execuções.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Discrepancy {
+    pub category: DiscrepancyCategory,
+    pub station: String,
+    #[serde(default)]
+    pub field: Option<ComparableField>,
+    pub description: String,
+}
+
+impl Discrepancy {
+    fn new(category: DiscrepancyCategory, station: &str, field: Option<ComparableField>, description: String) -> Self {
+        Self {
+            category,
+            station: station.to_string(),
+            field,
+            description,
+        }
+    }
+    
+    pub fn key(&self) -> (DiscrepancyCategory, &str, Option<ComparableField>) {
+        (self.category, &self.station, self.field)
+    }
+}
+
+impl ComparisonResult {
+    /// Todas as discrepâncias em aberto (sem as aceitas), numa lista única
+    pub fn discrepancies(&self) -> Vec<Discrepancy> {
+        use DiscrepancyCategory as C;
+        let mut list = Vec::new();
+        
+        for m in &self.status_mismatch {
+            list.push(Discrepancy::new(C::Status, &m.nome, Some(ComparableField::Status), m.description()));
+        }
+        for m in &self.regional_mismatch {
+            list.push(Discrepancy::new(C::Regional, &m.nome, Some(ComparableField::Regional), m.description()));
+        }
+        for diff in &self.diffs {
+            for f in diff.fields.iter().filter(|f| C::for_field(f.field) == C::Field) {
+                list.push(Discrepancy::new(C::Field, &diff.nome, Some(f.field), f.description()));
+            }
+        }
+        for link in &self.broken_links {
+            list.push(Discrepancy::new(C::BrokenLink, &link.id, None, link.description()));
+        }
+        for issue in &self.ip_issues {
+            list.push(Discrepancy::new(C::Ip, &issue.nome, None, issue.description()));
+        }
+        for duplicate in &self.duplicates {
+            list.push(Discrepancy::new(C::Duplicate, &duplicate.key, None, duplicate.description()));
+        }
+        for probable in &self.probable_matches {
+            list.push(Discrepancy::new(C::ProbableMatch, &probable.zabbix.nome, None, probable.description()));
+        }
+        for estacao in &self.only_in_zabbix {
+            list.push(Discrepancy::new(C::OnlyInZabbix, &estacao.nome, None, status_description(estacao)));
+        }
+        for estacao in &self.only_in_sharepoint {
+            list.push(Discrepancy::new(C::OnlyInSharepoint, &estacao.id, None, status_description(estacao)));
+        }
+        
+        list
+    }
+    
+    /// Discrepâncias em aberto por categoria; todas as categorias aparecem,
+    /// na ordem de declaração
+    pub fn category_counts(&self) -> IndexMap<DiscrepancyCategory, usize> {
+        let mut counts: IndexMap<DiscrepancyCategory, usize> = DiscrepancyCategory::ALL
+            .iter()
+            .map(|category| (*category, 0))
+            .collect();
+        for discrepancy in self.discrepancies() {
+            *counts.entry(discrepancy.category).or_default() += 1;
+        }
+        counts
+    }
+}
+
+/// Descrição de uma estação presente em só uma das origens
+pub fn status_description(estacao: &EstacaoInfo) -> String {
+    tf("description.status", &[("status", &estacao.status)])
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Duplicate {
+    /// Chave normalizada em comum
+    pub key: String,
+    pub source: DataSource,
+    pub records: Vec<EstacaoInfo>,
+}
+
+impl Duplicate {
+    pub fn description(&self) -> String {
+        tf("description.duplicate", &[("count", &self.records.len()), ("source", &self.source)])
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbableMatch {
+    pub zabbix: EstacaoInfo,
+    pub sharepoint: EstacaoInfo,
+    /// Entre 0 e 1
+    pub confidence: f64,
+}
+
+impl ProbableMatch {
+    pub fn description(&self) -> String {
+        format!("↔ {} ({})", self.sharepoint.id, percent(self.confidence * 100.0, 0))
+    }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatusMismatch {
     pub id: String,
     pub nome: String,
     pub zabbix_status: Status,
     pub sharepoint_status: Status,
-    pub zabbix_regional: Option<crate::models::common::Regional>,
-    pub sharepoint_regional: Option<crate::models::common::Regional>,
-}
\ No newline at end of file
+    pub zabbix_regional: Option<Regional>,
+    pub sharepoint_regional: Option<Regional>,
+}
+
+impl StatusMismatch {
+    pub fn description(&self) -> String {
+        tf("description.values", &[("zabbix", &self.zabbix_status), ("sharepoint", &self.sharepoint_status)])
+    }
+}
+
+/// Estação registrada em regionais diferentes, ou com regional em só uma
+/// das origens, qualquer que seja o status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionalMismatch {
+    pub id: String,
+    pub nome: String,
+    /// `None` quando a origem não informa a regional
+    pub zabbix_regional: Option<Regional>,
+    pub sharepoint_regional: Option<Regional>,
+    pub zabbix_status: Status,
+    pub sharepoint_status: Status,
+}
+
+impl RegionalMismatch {
+    /// Regionais exibidas (Zabbix, SharePoint), com `MISSING` no lado
+    /// ausente
+    pub fn regionals(&self) -> (String, String) {
+        let text = |r: &Option<Regional>| r.as_ref().map_or_else(|| MISSING.to_string(), |r| r.to_string());
+        (text(&self.zabbix_regional), text(&self.sharepoint_regional))
+    }
+    
+    pub fn description(&self) -> String {
+        let (zabbix, sharepoint) = self.regionals();
+        tf("description.values", &[("zabbix", &zabbix), ("sharepoint", &sharepoint)])
+    }
+}
+
+/// Divergências de uma estação presente nas duas origens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StationDiff {
+    pub id: String,
+    pub nome: String,
+    pub fields: Vec<FieldDiff>,
+    /// Registros comparados (ausentes em snapshots antigos)
+    #[serde(default)]
+    pub zabbix: Option<EstacaoInfo>,
+    #[serde(default)]
+    pub sharepoint: Option<EstacaoInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokenLink {
+    /// "ID de rede" do registro no SharePoint
+    pub id: String,
+    /// hostid extraído do link
+    pub hostid: String,
+    pub kind: BrokenLinkKind,
+}
+
+impl BrokenLink {
+    pub fn description(&self) -> String {
+        match &self.kind {
+            BrokenLinkKind::HostNotFound => tf("description.host_not_found", &[("hostid", &self.hostid)]),
+            BrokenLinkKind::NameMismatch(nome) => {
+                tf("description.name_mismatch", &[("hostid", &self.hostid), ("nome", nome)])
+            }
+            BrokenLinkKind::AlreadyLinked(other) => {
+                tf("description.already_linked", &[("hostid", &self.hostid), ("other", other)])
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrokenLinkKind {
+    /// Nenhum host do Zabbix tem esse hostid
+    HostNotFound,
+    /// O host existe, mas com outro nome
+    NameMismatch(String),
+    /// O host já está no "Link Zabbix" de outro registro (o "ID de rede"
+    /// indicado)
+    AlreadyLinked(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpIssue {
+    pub id: String,
+    pub nome: String,
+    /// Origem em que o problema foi encontrado
+    pub source: DataSource,
+    pub ip: Option<String>,
+    pub kind: IpIssueKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IpIssueKind {
+    /// Sem IP, embora a outra origem informe um
+    Empty,
+    /// Endereço malformado, de loopback ou não especificado
+    Invalid,
+    /// Mesmo IP usado pelas estações listadas
+    Duplicate(Vec<String>),
+}
+
+impl IpIssue {
+    fn empty(estacao: &EstacaoInfo) -> Self {
+        Self {
+            id: estacao.id.clone(),
+            nome: estacao.nome.clone(),
+            source: estacao.source,
+            ip: None,
+            kind: IpIssueKind::Empty,
+        }
+    }
+    
+    pub fn description(&self) -> String {
+        let ip = self.ip.as_deref().unwrap_or("");
+        match &self.kind {
+            IpIssueKind::Empty => tf("description.ip_empty", &[("source", &self.source)]),
+            IpIssueKind::Invalid => tf("description.ip_invalid", &[("source", &self.source), ("ip", &ip)]),
+            IpIssueKind::Duplicate(others) => tf(
+                "description.ip_duplicate",
+                &[("ip", &ip), ("source", &self.source), ("others", &others.join(", "))],
+            ),
+        }
+    }
+}
+
+/// Endereço válido para uma estação
+fn parse_ip(ip: &str) -> Option<IpAddr> {
+    let addr: IpAddr = ip.trim().parse().ok()?;
+    (!addr.is_loopback() && !addr.is_unspecified()).then_some(addr)
+}
+
+/// Forma do valor de um campo usada na comparação
+fn field_key(field: ComparableField, value: &str) -> String {
+    match field {
+        ComparableField::Regional => Regional::new(value.to_string()).code(),
+        ComparableField::Ip => match parse_ip(value) {
+            Some(addr) => addr.to_string(),
+            None => value.trim().to_lowercase(),
+        },
+        _ => value.trim().to_lowercase(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldDiff {
+    pub field: ComparableField,
+    pub zabbix_value: String,
+    pub sharepoint_value: String,
+}
+
+impl FieldDiff {
+    pub fn description(&self) -> String {
+        tf(
+            "description.field",
+            &[("field", &self.field), ("zabbix", &self.zabbix_value), ("sharepoint", &self.sharepoint_value)],
+        )
+    }
+}
+
+/// Categorias de discrepância do relatório
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscrepancyCategory {
+    Status,
+    Regional,
+    /// Demais campos comparados (tipo, IP, host)
+    Field,
+    BrokenLink,
+    Ip,
+    Duplicate,
+    ProbableMatch,
+    OnlyInZabbix,
+    OnlyInSharepoint,
+}
+
+/// O nome é o mesmo do JSON e de `--fail-on`
+impl Choice for DiscrepancyCategory {
+    const ALL: &'static [Self] = &[
+        DiscrepancyCategory::Status,
+        DiscrepancyCategory::Regional,
+        DiscrepancyCategory::Field,
+        DiscrepancyCategory::BrokenLink,
+        DiscrepancyCategory::Ip,
+        DiscrepancyCategory::Duplicate,
+        DiscrepancyCategory::ProbableMatch,
+        DiscrepancyCategory::OnlyInZabbix,
+        DiscrepancyCategory::OnlyInSharepoint,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            DiscrepancyCategory::Status => "status",
+            DiscrepancyCategory::Regional => "regional",
+            DiscrepancyCategory::Field => "field",
+            DiscrepancyCategory::BrokenLink => "broken_link",
+            DiscrepancyCategory::Ip => "ip",
+            DiscrepancyCategory::Duplicate => "duplicate",
+            DiscrepancyCategory::ProbableMatch => "probable_match",
+            DiscrepancyCategory::OnlyInZabbix => "only_in_zabbix",
+            DiscrepancyCategory::OnlyInSharepoint => "only_in_sharepoint",
+        }
+    }
+}
+
+impl DiscrepancyCategory {
+    /// Categoria em que aparece a divergência de um campo
+    pub fn for_field(field: ComparableField) -> Self {
+        match field {
+            ComparableField::Status => DiscrepancyCategory::Status,
+            ComparableField::Regional => DiscrepancyCategory::Regional,
+            _ => DiscrepancyCategory::Field,
+        }
+    }
+}
+
+impl fmt::Display for DiscrepancyCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self {
+            DiscrepancyCategory::Status => "category.status",
+            DiscrepancyCategory::Regional => "category.regional",
+            DiscrepancyCategory::Field => "category.field",
+            DiscrepancyCategory::BrokenLink => "category.broken_link",
+            DiscrepancyCategory::Ip => "category.ip",
+            DiscrepancyCategory::Duplicate => "category.duplicate",
+            DiscrepancyCategory::ProbableMatch => "category.probable_match",
+            DiscrepancyCategory::OnlyInZabbix => "category.only_in_zabbix",
+            DiscrepancyCategory::OnlyInSharepoint => "category.only_in_sharepoint",
+        };
+        f.write_str(&t(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::common::estacao;
+    
+    #[test]
+    fn test_regional_mismatch_with_same_status() {
+        let zabbix = vec![
+            estacao("RFeye002300", Status::Ativo, Some("GR01 - São Paulo"), DataSource::Zabbix),
+            estacao("RFeye002301", Status::Ativo, Some("GR03"), DataSource::Zabbix),
+            estacao("RFeye002302", Status::Ativo, None, DataSource::Zabbix),
+        ];
+        let sharepoint = vec![
+            estacao("RFeye002300", Status::Ativo, Some("GR01"), DataSource::SharePoint),
+            estacao("RFeye002301", Status::Ativo, Some("GR02"), DataSource::SharePoint),
+            estacao("RFeye002302", Status::Ativo, Some("GR02"), DataSource::SharePoint),
+        ];
+        
+        let result = Comparator::compare(zabbix, sharepoint, &Config::default());
+        
+        assert_eq!(result.matching.len(), 1);
+        assert!(result.status_mismatch.is_empty());
+        assert_eq!(result.regional_mismatch.len(), 2);
+        let mut ids: Vec<_> = result.regional_mismatch.iter().map(|m| m.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["2301", "2302"]);
+        assert_eq!(result.diffs.len(), 2);
+        assert_eq!(result.diffs[0].fields.len(), 1);
+        assert_eq!(result.diffs[0].fields[0].field, ComparableField::Regional);
+        // Regional só no SharePoint: o lado do Zabbix aparece como ausente
+        assert_eq!(result.diffs[1].fields[0].zabbix_value, MISSING);
+    }
+    
+    #[test]
+    fn test_match_by_linked_hostid() {
+        let mut z1 = estacao("RFeye002300", Status::Ativo, Some("GR01"), DataSource::Zabbix);
+        z1.zabbix_hostid = Some("10101".to_string());
+        let mut z2 = estacao("CWSM212030", Status::Ativo, Some("GR01"), DataSource::Zabbix);
+        z2.zabbix_hostid = Some("10102".to_string());
+        
+        // Nome diferente, mas o link aponta para o host
+        let mut sp1 = estacao("RFeye2300-A", Status::Ativo, Some("GR01"), DataSource::SharePoint);
+        sp1.zabbix_hostid = Some("10101".to_string());
+        // Link para host inexistente: cai na correspondência por nome
+        let mut sp2 = estacao("CWSM212030", Status::Ativo, Some("GR01"), DataSource::SharePoint);
+        sp2.zabbix_hostid = Some("99999".to_string());
+        
+        let result = Comparator::compare(vec![z1, z2], vec![sp1, sp2], &Config::default());
+        
+        assert!(result.only_in_zabbix.is_empty());
+        assert!(result.only_in_sharepoint.is_empty());
+        assert_eq!(result.broken_links.len(), 2);
+        assert!(matches!(
+            &result.broken_links[0].kind,
+            BrokenLinkKind::HostNotFound
+        ));
+        assert!(matches!(
+            &result.broken_links[1].kind,
+            BrokenLinkKind::NameMismatch(nome) if nome == "RFeye002300"
+        ));
+        assert_eq!(result.diffs.len(), 1);
+        assert_eq!(result.diffs[0].fields[0].field, ComparableField::HostLink);
+    }
+    
+    #[test]
+    fn test_host_linked_twice() {
+        let mut z = estacao("RFeye002300", Status::Ativo, None, DataSource::Zabbix);
+        z.zabbix_hostid = Some("10101".to_string());
+        let mut sp1 = estacao("RFeye002300", Status::Ativo, None, DataSource::SharePoint);
+        sp1.zabbix_hostid = Some("10101".to_string());
+        let mut sp2 = estacao("RFeye002399", Status::Ativo, None, DataSource::SharePoint);
+        sp2.zabbix_hostid = Some("10101".to_string());
+        
+        let result = Comparator::compare(vec![z], vec![sp1, sp2], &Config::default());
+        
+        assert_eq!(result.matching.len(), 1);
+        assert_eq!(result.broken_links.len(), 1);
+        assert_eq!(result.broken_links[0].id, "RFeye002399");
+        assert_eq!(result.broken_links[0].kind, BrokenLinkKind::AlreadyLinked("RFeye002300".to_string()));
+        // Sem o host, o registro segue para a correspondência por nome
+        assert_eq!(result.only_in_sharepoint.len(), 1);
+    }
+    
+    #[test]
+    fn test_ip_issues() {
+        let with_ip = |id: &str, ip: Option<&str>, source: DataSource| {
+            let mut e = estacao(id, Status::Ativo, Some("GR01"), source);
+            e.ip = ip.map(str::to_string);
+            e
+        };
+        let zabbix = vec![
+            with_ip("RFeye002300", Some("10.0.0.1"), DataSource::Zabbix),
+            with_ip("RFeye002301", Some("10.0.0.1"), DataSource::Zabbix),
+            with_ip("RFeye002302", Some("10.0.0.3"), DataSource::Zabbix),
+        ];
+        let sharepoint = vec![
+            with_ip("RFeye002300", Some("10.0.0.9"), DataSource::SharePoint),
+            with_ip("RFeye002301", Some("10.0.0.1 "), DataSource::SharePoint),
+            with_ip("RFeye002302", None, DataSource::SharePoint),
+            with_ip("RFeye002303", Some("300.0.0.1"), DataSource::SharePoint),
+        ];
+        
+        let result = Comparator::compare(zabbix, sharepoint, &Config::default());
+        
+        let ip_diffs: Vec<_> = result
+            .diffs
+            .iter()
+            .filter(|d| d.fields.iter().any(|f| f.field == ComparableField::Ip))
+            .map(|d| d.id.as_str())
+            .collect();
+        assert_eq!(ip_diffs, vec!["2300"]);
+        
+        let kinds: Vec<_> = result
+            .ip_issues
+            .iter()
+            .map(|i| match &i.kind {
+                IpIssueKind::Empty => format!("{} vazio", i.id),
+                IpIssueKind::Invalid => format!("{} inválido", i.id),
+                IpIssueKind::Duplicate(others) => format!("{} repetido com {}", i.id, others.join(",")),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "RFeye002300 repetido com RFeye002301",
+                "RFeye002301 repetido com RFeye002300",
+                "RFeye002302 vazio",
+                "RFeye002303 inválido",
+            ]
+        );
+    }
+    
+    #[test]
+    fn test_probable_matches() {
+        let zabbix = vec![
+            estacao("RFeye-2300", Status::Ativo, None, DataSource::Zabbix),
+            estacao("RFeye002301", Status::Ativo, None, DataSource::Zabbix),
+            estacao("MIAer-SP", Status::Ativo, None, DataSource::Zabbix),
+        ];
+        let sharepoint = vec![
+            // Outra série: não é sugerido
+            estacao("RFeye 2302", Status::Ativo, None, DataSource::SharePoint),
+            estacao("RFeye_2300_A", Status::Ativo, None, DataSource::SharePoint),
+            estacao("MIAer SP 1", Status::Ativo, None, DataSource::SharePoint),
+        ];
+        
+        let result = Comparator::compare(zabbix, sharepoint, &Config::default());
+        
+        let pairs: Vec<_> = result
+            .probable_matches
+            .iter()
+            .map(|m| (m.zabbix.nome.as_str(), m.sharepoint.id.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![("RFeye-2300", "RFeye_2300_A"), ("MIAer-SP", "MIAer SP 1")]
+        );
+        assert!(result.probable_matches[0].confidence > 0.9);
+        assert_eq!(result.only_in_zabbix.len(), 1);
+        assert_eq!(result.only_in_sharepoint.len(), 1);
+    }
+    
+    #[test]
+    fn test_duplicates_policy() {
+        let zabbix = vec![
+            estacao("RFeye002300", Status::Ativo, None, DataSource::Zabbix),
+            estacao("rfeye2300", Status::Defeito, None, DataSource::Zabbix),
+        ];
+        let sharepoint = vec![estacao("RFeye002300", Status::Defeito, None, DataSource::SharePoint)];
+        
+        let mut config = Config::default();
+        let result = Comparator::compare(zabbix.clone(), sharepoint.clone(), &config);
+        assert_eq!(result.duplicates.len(), 1);
+        assert_eq!(result.duplicates[0].key, "2300");
+        assert_eq!(result.duplicates[0].records.len(), 2);
+        assert_eq!(result.matching.len(), 1);
+        
+        config.compare.duplicate_policy = DuplicatePolicy::First;
+        let result = Comparator::compare(zabbix.clone(), sharepoint.clone(), &config);
+        assert_eq!(result.status_mismatch.len(), 1);
+        
+        config.compare.duplicate_policy = DuplicatePolicy::Skip;
+        let result = Comparator::compare(zabbix, sharepoint, &config);
+        assert_eq!(result.duplicates.len(), 1);
+        assert_eq!(result.only_in_sharepoint.len(), 1);
+        assert!(result.only_in_zabbix.is_empty());
+    }
+    
+    #[test]
+    fn test_aliases_applied_before_normalization() {
+        let mut antigo = estacao("RFeye-Brasilia-Antigo", Status::Ativo, None, DataSource::Zabbix);
+        antigo.zabbix_hostid = Some("10105".to_string());
+        let zabbix = vec![antigo, estacao("MIAer-DF", Status::Ativo, None, DataSource::Zabbix)];
+        let sharepoint = vec![
+            estacao("RFeye002310", Status::Ativo, None, DataSource::SharePoint),
+            estacao("MIAer-Brasilia", Status::Ativo, None, DataSource::SharePoint),
+        ];
+        let aliases = Aliases::from_toml_str(
+            r#"
+            [[alias]]
+            zabbix = "10105"
+            sharepoint = "RFeye002310"
+            
+            [[alias]]
+            zabbix = "miaer-df"
+            sharepoint = "MIAer-Brasilia"
+            "#,
+        )
+        .unwrap();
+        
+        let result = Comparator::compare_with_aliases(zabbix, sharepoint, &Config::default(), &aliases);
+        
+        assert_eq!(result.matching.len(), 2);
+        assert!(result.only_in_zabbix.is_empty());
+        assert!(result.only_in_sharepoint.is_empty());
+    }
+    
+    #[test]
+    fn test_status_rules() {
+        let mut litigio = estacao("RFeye002302", Status::Ativo, None, DataSource::SharePoint);
+        litigio.litigio = Some("Processo 123".to_string());
+        let zabbix = vec![
+            estacao("RFeye002300", Status::Defeito, None, DataSource::Zabbix),
+            estacao("RFeye002301", Status::Ativo, None, DataSource::Zabbix),
+            estacao("RFeye002302", Status::Litigio, None, DataSource::Zabbix),
+        ];
+        let sharepoint = vec![
+            estacao("RFeye002300", Status::Manutencao, None, DataSource::SharePoint),
+            estacao("RFeye002301", Status::Manutencao, None, DataSource::SharePoint),
+            litigio,
+            estacao("RFeye002303", Status::Baixa, None, DataSource::SharePoint),
+        ];
+        
+        let result = Comparator::compare(zabbix, sharepoint, &Config::default());
+        
+        assert_eq!(result.matching.len(), 2);
+        assert_eq!(result.status_mismatch.len(), 1);
+        assert_eq!(result.status_mismatch[0].id, "2301");
+        assert_eq!(result.expected_absent.len(), 1);
+        assert!(result.only_in_sharepoint.is_empty());
+    }
+    
+    #[test]
+    fn test_selected_fields_only() {
+        let mut z = estacao("RFeye002300", Status::Defeito, Some("GR01"), DataSource::Zabbix);
+        let mut sp = estacao("RFeye002300", Status::Ativo, Some("GR01"), DataSource::SharePoint);
+        z.tipo = TipoEstacao::ERMx;
+        sp.ip = Some("10.0.0.1".to_string());
+        
+        let all = Comparator::diff_fields(&z, &sp, ComparableField::ALL, &[]);
+        let fields: Vec<_> = all.iter().map(|d| d.field).collect();
+        assert_eq!(fields, vec![ComparableField::Status, ComparableField::Tipo]);
+        assert_eq!(all[1].zabbix_value, "ERM-x");
+        
+        let mut config = Config::default();
+        config.compare.fields = vec![ComparableField::Regional];
+        let result = Comparator::compare(vec![z], vec![sp], &config);
+        assert_eq!(result.matching.len(), 1);
+    }
+    
+    #[test]
+    fn test_unknown_status_not_compared() {
+        let zabbix = vec![estacao("RFeye002301", Status::Defeito, None, DataSource::Zabbix)];
+        let sharepoint = vec![estacao("RFeye002301", Status::Desconhecido, None, DataSource::SharePoint)];
+        
+        let result = Comparator::compare(zabbix, sharepoint, &Config::default());
+        
+        assert!(result.status_mismatch.is_empty());
+        assert_eq!(result.matching.len(), 1);
+        assert_eq!(result.category_counts()[&DiscrepancyCategory::Status], 0);
+    }
+}
@@ -0,0 +1,251 @@
+//! Recorte das estações por regional, status, tipo, UF e padrão de ID,
+//! aplicado antes da agregação e da comparação, para cada regional ter
+//! relatórios só com as suas estações.
+
+use crate::{
+    config::Config,
+    models::common::{EstacaoInfo, Regional, Status},
+    parsers::diagnostic::ParseDiagnostic,
+    services::{aliases::Aliases, comparator::Comparator},
+    utils::normalizer::Normalizer,
+};
+use std::collections::HashSet;
+
+/// Critérios do recorte. Lista vazia não restringe; dentro de um critério
+/// basta casar um dos valores, e a estação precisa atender a todos.
+#[derive(Debug, Clone, Default)]
+pub struct StationFilter {
+    /// Comparadas pelo código ("GR01" casa "GR01 - São Paulo")
+    pub regionals: Vec<String>,
+    pub statuses: Vec<Status>,
+    /// Nomes de tipo, sem diferenciar maiúsculas nem pontuação
+    pub tipos: Vec<String>,
+    pub ufs: Vec<String>,
+    /// Padrões com `*` e `?`, testados no ID e no nome
+    pub id_patterns: Vec<String>,
+}
+
+impl StationFilter {
+    pub fn is_empty(&self) -> bool {
+        self.regionals.is_empty()
+            && self.statuses.is_empty()
+            && self.tipos.is_empty()
+            && self.ufs.is_empty()
+            && self.id_patterns.is_empty()
+    }
+
+    pub fn matches(&self, estacao: &EstacaoInfo) -> bool {
+        let regional = self.regionals.is_empty()
+            || estacao.regional.as_ref().is_some_and(|r| {
+                self.regionals
+                    .iter()
+                    .any(|wanted| Regional::new(wanted.clone()).same_as(r))
+            });
+        let status = self.statuses.is_empty() || self.statuses.contains(&estacao.status);
+        let tipo = self.tipos.is_empty()
+            || self
+                .tipos
+                .iter()
+                .any(|wanted| tipo_key(wanted) == tipo_key(&estacao.tipo.to_string()));
+        let uf = self.ufs.is_empty()
+            || estacao
+                .uf
+                .as_deref()
+                .is_some_and(|uf| self.ufs.iter().any(|wanted| wanted.trim().eq_ignore_ascii_case(uf.trim())));
+        let id = self.id_patterns.is_empty()
+            || self
+                .id_patterns
+                .iter()
+                .any(|pattern| glob_match(pattern, &estacao.id) || glob_match(pattern, &estacao.nome));
+
+        regional && status && tipo && uf && id
+    }
+
+    /// Estações de uma origem que atendem ao recorte
+    pub fn apply(&self, estacoes: Vec<EstacaoInfo>) -> Vec<EstacaoInfo> {
+        if self.is_empty() {
+            return estacoes;
+        }
+        estacoes.into_iter().filter(|e| self.matches(e)).collect()
+    }
+
+    /// Recorte das duas origens para a comparação. Filtrar cada lado por
+    /// conta própria transformaria divergências (status ou regional
+    /// diferentes, UF que só o SharePoint informa) em estações "apenas
+    /// em" uma origem; por isso uma estação fica no recorte, dos dois
+    /// lados, quando o registro de qualquer origem atende aos critérios.
+    pub fn scope(
+        &self,
+        zabbix: Vec<EstacaoInfo>,
+        sharepoint: Vec<EstacaoInfo>,
+        config: &Config,
+        aliases: &Aliases,
+    ) -> (Vec<EstacaoInfo>, Vec<EstacaoInfo>) {
+        if self.is_empty() {
+            return (zabbix, sharepoint);
+        }
+
+        let zabbix_key = |e: &EstacaoInfo| Comparator::zabbix_key(e, config, aliases);
+        let sharepoint_key = |e: &EstacaoInfo| Normalizer::normalize_id(&e.id, config);
+
+        // Chaves normalizadas e hostids ("Link Zabbix") no recorte
+        let mut keys = HashSet::new();
+        let mut hostids = HashSet::new();
+        for estacao in zabbix.iter().filter(|e| self.matches(e)) {
+            keys.insert(zabbix_key(estacao));
+            hostids.extend(estacao.zabbix_hostid.clone());
+        }
+        for estacao in sharepoint.iter().filter(|e| self.matches(e)) {
+            keys.insert(sharepoint_key(estacao));
+            hostids.extend(estacao.zabbix_hostid.clone());
+        }
+
+        let in_scope = |key: String, hostid: &Option<String>| {
+            keys.contains(&key) || hostid.as_ref().is_some_and(|h| hostids.contains(h))
+        };
+        let zabbix = zabbix
+            .into_iter()
+            .filter(|e| in_scope(zabbix_key(e), &e.zabbix_hostid))
+            .collect();
+        let sharepoint = sharepoint
+            .into_iter()
+            .filter(|e| in_scope(sharepoint_key(e), &e.zabbix_hostid))
+            .collect();
+        (zabbix, sharepoint)
+    }
+
+    /// Separa os diagnósticos de leitura do SharePoint pelo recorte já
+    /// aplicado em `sharepoint`: ficam os das estações no recorte e saem
+    /// os das demais. Linhas que não geraram estação (ID vazio, linha
+    /// descartada) não têm como ser atribuídas e voltam à parte, no
+    /// segundo vetor. Sem recorte, todos ficam no primeiro.
+    pub fn diagnostics(
+        &self,
+        diagnostics: Vec<ParseDiagnostic>,
+        sharepoint: &[EstacaoInfo],
+        config: &Config,
+    ) -> (Vec<ParseDiagnostic>, Vec<ParseDiagnostic>) {
+        if self.is_empty() {
+            return (diagnostics, Vec::new());
+        }
+
+        let keys: HashSet<String> = sharepoint
+            .iter()
+            .map(|e| Normalizer::normalize_id(&e.id, config))
+            .collect();
+        let mut in_scope = Vec::new();
+        let mut unattributed = Vec::new();
+        for diagnostic in diagnostics {
+            match &diagnostic.station {
+                Some(id) if keys.contains(&Normalizer::normalize_id(id, config)) => in_scope.push(diagnostic),
+                Some(_) => {}
+                None => unattributed.push(diagnostic),
+            }
+        }
+        (in_scope, unattributed)
+    }
+}
+
+/// "ERM-x", "ermx" e "ERMx" são o mesmo tipo
+fn tipo_key(tipo: &str) -> String {
+    Normalizer::normalize_text(tipo)
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// `*` casa qualquer sequência e `?` um caractere, sem diferenciar
+/// maiúsculas
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Último `*` visto e a posição do texto em que ele passou a valer
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // Retrocede: o `*` absorve mais um caractere
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::common::{estacao, DataSource};
+    use crate::parsers::diagnostic::DiagnosticReason;
+
+    #[test]
+    fn test_filter_and_scope() {
+        assert!(glob_match("rfeye0023*", "RFeye002300"));
+        assert!(glob_match("*23?0", "RFeye002300"));
+        assert!(!glob_match("CWSM*", "RFeye002300"));
+
+        let mut sp = estacao("RFeye002300", Status::Ativo, Some("GR01 - São Paulo"), DataSource::SharePoint);
+        sp.uf = Some("SP".to_string());
+        let filter = StationFilter {
+            regionals: vec!["gr01".to_string()],
+            tipos: vec!["rfeye".to_string()],
+            ..Default::default()
+        };
+        assert!(filter.matches(&sp));
+        let by_uf = StationFilter { ufs: vec!["sp".to_string()], ..Default::default() };
+        assert!(by_uf.matches(&sp));
+        assert!(!by_uf.matches(&estacao("RFeye002300", Status::Ativo, Some("GR01"), DataSource::Zabbix)));
+
+        // A UF só existe no SharePoint, mas o host correspondente entra no
+        // recorte; os demais hosts ficam de fora
+        let zabbix = vec![
+            estacao("RFeye002300", Status::Defeito, Some("GR02"), DataSource::Zabbix),
+            estacao("RFeye002301", Status::Ativo, Some("GR01"), DataSource::Zabbix),
+        ];
+        let (zabbix, sharepoint) = by_uf.scope(zabbix, vec![sp], &Config::default(), &Aliases::default());
+        assert_eq!(zabbix.len(), 1);
+        assert_eq!(zabbix[0].nome, "RFeye002300");
+        assert_eq!(sharepoint.len(), 1);
+
+        let by_status = StationFilter { statuses: vec![Status::Defeito], ..Default::default() };
+        let (zabbix, sharepoint) = by_status.scope(zabbix, sharepoint, &Config::default(), &Aliases::default());
+        assert_eq!((zabbix.len(), sharepoint.len()), (1, 1));
+    }
+
+    #[test]
+    fn test_diagnostics_follow_scope() {
+        let config = Config::default();
+        let diagnostics = vec![
+            ParseDiagnostic::new(2, "Situação do Equipamento", "x", DiagnosticReason::UnknownStatus)
+                .with_station("RFeye002300"),
+            ParseDiagnostic::new(3, "Situação do Equipamento", "y", DiagnosticReason::UnknownStatus)
+                .with_station("RFeye002301"),
+            ParseDiagnostic::new(4, "Situação do Equipamento", "z", DiagnosticReason::UnknownStatusSkipped),
+        ];
+        let sharepoint = vec![
+            estacao("RFeye002300", Status::Desconhecido, Some("GR01"), DataSource::SharePoint),
+            estacao("RFeye002301", Status::Desconhecido, Some("GR02"), DataSource::SharePoint),
+        ];
+
+        let (all, unattributed) = StationFilter::default().diagnostics(diagnostics.clone(), &sharepoint, &config);
+        assert_eq!((all.len(), unattributed.len()), (3, 0));
+
+        let filter = StationFilter { regionals: vec!["GR01".to_string()], ..Default::default() };
+        let (in_scope, unattributed) = filter.diagnostics(diagnostics, &filter.apply(sharepoint), &config);
+        assert_eq!(in_scope.len(), 1);
+        assert_eq!(in_scope[0].row, 2);
+        assert_eq!(unattributed.len(), 1);
+        assert_eq!(unattributed[0].row, 4);
+    }
+}
@@ -0,0 +1,307 @@
+//! Histórico de comparações: cada execução de `compare --save` grava um
+//! arquivo JSON com as entradas normalizadas e o resultado, nomeado pelo
+//! horário da execução (`AAAAMMDD-HHMMSS`, com `-02`, `-03`... quando há
+//! mais de uma gravação no mesmo segundo).
+
+use crate::{
+    error::{AppError, Result},
+    i18n::{t, tf},
+    models::common::EstacaoInfo,
+    services::comparator::{ComparisonResult, Discrepancy},
+};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// Versão do formato dos arquivos gravados
+pub const SNAPSHOT_VERSION: u32 = 1;
+const ID_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    pub created_at: DateTime<Local>,
+    pub zabbix: Vec<EstacaoInfo>,
+    pub sharepoint: Vec<EstacaoInfo>,
+    pub discrepancies: Vec<Discrepancy>,
+    pub result: ComparisonResult,
+}
+
+impl Snapshot {
+    pub fn new(zabbix: Vec<EstacaoInfo>, sharepoint: Vec<EstacaoInfo>, result: ComparisonResult) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            created_at: Local::now(),
+            zabbix,
+            sharepoint,
+            discrepancies: result.discrepancies(),
+            result,
+        }
+    }
+
+    pub fn id(&self) -> String {
+        self.created_at.format(ID_FORMAT).to_string()
+    }
+}
+
+/// Só o necessário para comparar execuções, sem ler as entradas
+#[derive(Debug, Clone, Deserialize)]
+pub struct SnapshotSummary {
+    #[serde(skip)]
+    pub id: String,
+    pub created_at: DateTime<Local>,
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+/// Diretório com os snapshots
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+impl SnapshotStore {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    /// Grava o snapshot sem sobrescrever outro do mesmo segundo: o ID
+    /// ganha um sufixo que mantém a ordem e o prefixo de data
+    pub fn save(&self, snapshot: &Snapshot) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let content = serde_json::to_string(snapshot)?;
+        let base = snapshot.id();
+        let mut id = base.clone();
+        let mut attempt = 1;
+
+        loop {
+            let path = self.path(&id);
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(content.as_bytes())?;
+                    return Ok(path);
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    attempt += 1;
+                    id = format!("{}-{:02}", base, attempt);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// IDs gravados, do mais antigo para o mais recente
+    pub fn list(&self) -> Result<Vec<String>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut ids: Vec<String> = fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    pub fn load(&self, id: &str) -> Result<Snapshot> {
+        let content = fs::read_to_string(self.path(id))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn load_summary(&self, id: &str) -> Result<SnapshotSummary> {
+        let content = fs::read_to_string(self.path(id))?;
+        let mut summary: SnapshotSummary = serde_json::from_str(&content)?;
+        summary.id = id.to_string();
+        Ok(summary)
+    }
+
+    fn path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    /// Compara dois snapshots (padrão: os dois mais recentes). A idade de
+    /// cada discrepância conta desde a primeira execução de uma sequência
+    /// ininterrupta em que ela aparece.
+    pub fn diff(&self, from: Option<&str>, to: Option<&str>) -> Result<HistoryDiff> {
+        let ids = self.list()?;
+        let position = |id: &str| {
+            ids.iter()
+                .position(|i| i == id)
+                .ok_or_else(|| AppError::Generic(tf("history.not_found", &[("id", &id)])))
+        };
+
+        let to_index = match to {
+            Some(id) => position(id)?,
+            None => ids
+                .len()
+                .checked_sub(1)
+                .ok_or_else(|| AppError::Generic(t("history.empty")))?,
+        };
+        let from_index = match from {
+            Some(id) => position(id)?,
+            None => to_index
+                .checked_sub(1)
+                .ok_or_else(|| AppError::Generic(t("history.need_two")))?,
+        };
+        if from_index >= to_index {
+            return Err(AppError::Generic(t("history.order")));
+        }
+
+        let summaries = ids[..=to_index]
+            .iter()
+            .map(|id| self.load_summary(id))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(HistoryDiff::between(&summaries, from_index, to_index))
+    }
+}
+
+/// Discrepância com a data em que passou a aparecer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgedDiscrepancy {
+    pub discrepancy: Discrepancy,
+    pub since: DateTime<Local>,
+    /// Idade em dias na data de referência
+    pub age_days: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryDiff {
+    pub from: DateTime<Local>,
+    pub to: DateTime<Local>,
+    /// Presentes no final e não no inicial
+    pub appeared: Vec<AgedDiscrepancy>,
+    /// Presentes no inicial e não no final (idade em `from`)
+    pub resolved: Vec<AgedDiscrepancy>,
+    /// Presentes nos dois
+    pub persisted: Vec<AgedDiscrepancy>,
+}
+
+impl HistoryDiff {
+    fn between(summaries: &[SnapshotSummary], from_index: usize, to_index: usize) -> Self {
+        let from = &summaries[from_index];
+        let to = &summaries[to_index];
+        let from_keys: HashMap<_, _> = from.discrepancies.iter().map(|d| (d.key(), d)).collect();
+        let to_keys: HashMap<_, _> = to.discrepancies.iter().map(|d| (d.key(), d)).collect();
+
+        let aged = |discrepancy: &Discrepancy, index: usize| {
+            let since = Self::first_seen(summaries, index, discrepancy);
+            AgedDiscrepancy {
+                discrepancy: discrepancy.clone(),
+                since,
+                age_days: (summaries[index].created_at - since).num_days(),
+            }
+        };
+
+        let mut appeared = Vec::new();
+        let mut persisted = Vec::new();
+        for discrepancy in &to.discrepancies {
+            if from_keys.contains_key(&discrepancy.key()) {
+                persisted.push(aged(discrepancy, to_index));
+            } else {
+                appeared.push(aged(discrepancy, to_index));
+            }
+        }
+        let resolved = from
+            .discrepancies
+            .iter()
+            .filter(|d| !to_keys.contains_key(&d.key()))
+            .map(|d| aged(d, from_index))
+            .collect();
+
+        Self {
+            from: from.created_at,
+            to: to.created_at,
+            appeared,
+            resolved,
+            persisted,
+        }
+    }
+
+    fn first_seen(summaries: &[SnapshotSummary], index: usize, discrepancy: &Discrepancy) -> DateTime<Local> {
+        let key = discrepancy.key();
+        let mut first = index;
+        while first > 0
+            && summaries[first - 1]
+                .discrepancies
+                .iter()
+                .any(|d| d.key() == key)
+        {
+            first -= 1;
+        }
+        summaries[first].created_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::compare::ComparableField;
+    use crate::services::comparator::DiscrepancyCategory;
+    use chrono::TimeZone;
+
+    fn summary(day: u32, stations: &[&str]) -> SnapshotSummary {
+        SnapshotSummary {
+            id: format!("202601{:02}", day),
+            created_at: Local.with_ymd_and_hms(2026, 1, day, 8, 0, 0).unwrap(),
+            discrepancies: stations
+                .iter()
+                .map(|s| Discrepancy {
+                    category: DiscrepancyCategory::Status,
+                    station: s.to_string(),
+                    field: Some(ComparableField::Status),
+                    description: String::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_with_ages() {
+        let summaries = vec![
+            summary(1, &["A", "B"]),
+            summary(5, &["A", "B", "C"]),
+            summary(10, &["A", "C", "D"]),
+        ];
+
+        let diff = HistoryDiff::between(&summaries, 1, 2);
+        let stations = |list: &[AgedDiscrepancy]| -> Vec<(String, i64)> {
+            list.iter()
+                .map(|a| (a.discrepancy.station.clone(), a.age_days))
+                .collect()
+        };
+
+        assert_eq!(stations(&diff.appeared), vec![("D".to_string(), 0)]);
+        assert_eq!(stations(&diff.resolved), vec![("B".to_string(), 4)]);
+        assert_eq!(
+            stations(&diff.persisted),
+            vec![("A".to_string(), 9), ("C".to_string(), 5)]
+        );
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(dir.path());
+        assert!(store.diff(None, None).is_err());
+
+        let result = crate::services::comparator::Comparator::compare(
+            Vec::new(),
+            Vec::new(),
+            &crate::config::Config::default(),
+        );
+        let snapshot = Snapshot::new(Vec::new(), Vec::new(), result);
+        store.save(&snapshot).unwrap();
+        // Mesmo segundo: o segundo arquivo não sobrescreve o primeiro
+        store.save(&snapshot).unwrap();
+
+        let second = format!("{}-02", snapshot.id());
+        assert_eq!(store.list().unwrap(), vec![snapshot.id(), second.clone()]);
+        assert_eq!(store.load(&snapshot.id()).unwrap().version, SNAPSHOT_VERSION);
+        assert_eq!(store.load(&second).unwrap().version, SNAPSHOT_VERSION);
+    }
+}
@@ -0,0 +1,101 @@
+//! Servidor HTTP local para testes: responde às requisições, na ordem,
+//! com as respostas gravadas e guarda o que recebeu. Ocorrências de
+//! `{{BASE_URL}}` nas respostas são trocadas pelo endereço do servidor.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub request_line: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn start(responses: Vec<String>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+        let base_url = url.clone();
+
+        let handle = thread::spawn(move || {
+            for body in responses {
+                let body = body.replace("{{BASE_URL}}", &base_url);
+                let (mut stream, _) = match listener.accept() {
+                    Ok(conn) => conn,
+                    Err(_) => return,
+                };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+
+                let mut headers = Vec::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some((k, v)) = line.split_once(':') {
+                        if k.eq_ignore_ascii_case("content-length") {
+                            content_length = v.trim().parse().unwrap_or(0);
+                        }
+                        headers.push((k.trim().to_string(), v.trim().to_string()));
+                    }
+                }
+
+                let mut buf = vec![0; content_length];
+                reader.read_exact(&mut buf).unwrap();
+
+                recorded.lock().unwrap().push(RecordedRequest {
+                    request_line: request_line.trim_end().to_string(),
+                    headers,
+                    body: String::from_utf8_lossy(&buf).to_string(),
+                });
+
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        Self {
+            url,
+            requests,
+            handle: Some(handle),
+        }
+    }
+
+    /// Aguarda o consumo de todas as respostas e devolve as requisições
+    pub fn finish(mut self) -> Vec<RecordedRequest> {
+        if let Some(handle) = self.handle.take() {
+            handle.join().unwrap();
+        }
+        self.requests.lock().unwrap().clone()
+    }
+}
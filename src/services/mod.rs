@@ -1,7 +1,30 @@
 pub mod aggregator;
+pub mod aliases;
 pub mod comparator;
 pub mod data_loader;
+pub mod filter;
+pub mod history;
+pub mod sharepoint_client;
+pub mod thresholds;
+pub mod trend;
+pub mod waivers;
+pub mod zabbix_client;
+
+#[cfg(test)]
+pub(crate) mod mock_server;
 
 pub use aggregator::Aggregator;
-pub use comparator::{ComparisonResult, Comparator, StatusMismatch};
-pub use data_loader::DataLoader;
\ No newline at end of file
+pub use aliases::{Alias, Aliases};
+pub use comparator::{
+    BrokenLink, BrokenLinkKind, ComparisonResult, Comparator, Discrepancy, DiscrepancyCategory,
+    Duplicate, FieldDiff, IpIssue, IpIssueKind, ProbableMatch, RegionalMismatch, StationDiff,
+    StatusMismatch,
+};
+pub use data_loader::DataLoader;
+pub use filter::StationFilter;
+pub use history::{HistoryDiff, Snapshot, SnapshotStore};
+pub use sharepoint_client::SharePointClient;
+pub use thresholds::{Threshold, ThresholdCheck, Violation};
+pub use trend::{Trend, TrendPeriod, TrendSeries};
+pub use waivers::{AcceptedItem, Waiver, Waivers};
+pub use zabbix_client::{ZabbixAuth, ZabbixClient};
\ No newline at end of file
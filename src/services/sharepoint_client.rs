@@ -0,0 +1,268 @@
+use crate::{
+    config::SHAREPOINT_PAGE_SIZE,
+    error::{AppError, Result},
+    i18n::{t, tf},
+    models::sharepoint::{SharePointApi, SharePointField, SharePointRecord},
+    parsers::csv_parser::ColumnMapping,
+};
+use serde_json::Value;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Cliente de leitura dos itens da lista de estações
+pub struct SharePointClient {
+    agent: ureq::Agent,
+    api: SharePointApi,
+    site_url: String,
+    list: String,
+    token: Option<String>,
+    page_size: usize,
+    columns: ColumnMapping,
+}
+
+impl SharePointClient {
+    /// `site_url` é a URL do site (REST) ou `.../v1.0/sites/{site-id}` (Graph);
+    /// `list` é o título da lista (REST) ou seu ID/nome (Graph).
+    pub fn new(api: SharePointApi, site_url: &str, list: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(60))
+                .build(),
+            api,
+            site_url: site_url.trim_end_matches('/').to_string(),
+            list: list.to_string(),
+            token: None,
+            page_size: SHAREPOINT_PAGE_SIZE,
+            columns: ColumnMapping::default(),
+        }
+    }
+
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    pub fn with_columns(mut self, columns: ColumnMapping) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Busca todos os itens, seguindo os links de paginação do OData
+    pub fn fetch_records(&self) -> Result<Vec<SharePointRecord>> {
+        let mut records = Vec::new();
+        let mut next = Some(self.first_page_url());
+        let mut page = 0;
+
+        while let Some(url) = next {
+            page += 1;
+            debug!("Buscando página {} da lista do SharePoint", page);
+            let response = self.get(&url)?;
+            let (page_records, next_link) = Self::parse_page(&response, &self.columns)?;
+            records.extend(page_records);
+            next = next_link;
+        }
+
+        info!("{}", tf("log.sharepoint_items", &[("count", &records.len()), ("pages", &page)]));
+        Ok(Self::number_items(records))
+    }
+
+    /// Lê uma resposta salva da API (modo offline). Links de paginação são
+    /// ignorados.
+    pub fn parse_saved_response(json_data: &str, columns: &ColumnMapping) -> Result<Vec<SharePointRecord>> {
+        let response: Value = serde_json::from_str(json_data)?;
+        Ok(Self::number_items(Self::parse_page(&response, columns)?.0))
+    }
+
+    /// Posição de cada item na lista, usada nos diagnósticos de leitura
+    fn number_items(mut records: Vec<SharePointRecord>) -> Vec<SharePointRecord> {
+        for (index, record) in records.iter_mut().enumerate() {
+            record.linha = Some(index as u64 + 1);
+        }
+        records
+    }
+
+    /// O `$select` pede as colunas do mapeamento efetivo
+    fn first_page_url(&self) -> String {
+        let select = self.columns.select_names().join(",");
+
+        match self.api {
+            SharePointApi::Rest => format!(
+                "{}/_api/web/lists/getbytitle('{}')/items?$select={},Modified,Editor/Title&$expand=Editor&$top={}",
+                self.site_url,
+                percent_encode(&self.list.replace('\'', "''")),
+                select,
+                self.page_size
+            ),
+            // "Modificado por" vem de lastModifiedBy, fora de "fields"
+            SharePointApi::Graph => format!(
+                "{}/lists/{}/items?$expand=fields($select={},Modified)&$top={}",
+                self.site_url,
+                percent_encode(&self.list),
+                select,
+                self.page_size
+            ),
+        }
+    }
+
+    fn get(&self, url: &str) -> Result<Value> {
+        let mut request = self.agent.get(url);
+        if self.api == SharePointApi::Rest {
+            request = request.set("Accept", "application/json;odata=nometadata");
+        }
+        if let Some(token) = &self.token {
+            request = request.set("Authorization", &format!("Bearer {}", token));
+        }
+
+        let response = request.call().map_err(|e| AppError::Http(e.to_string()))?;
+        Ok(response.into_json()?)
+    }
+
+    /// Extrai os itens e o link da próxima página. Aceita as respostas do
+    /// Graph (`value[].fields`), do REST com `odata=nometadata` (`value[]`)
+    /// e do REST `odata=verbose` (`d.results[]`).
+    fn parse_page(response: &Value, columns: &ColumnMapping) -> Result<(Vec<SharePointRecord>, Option<String>)> {
+        let (items, next) = if let Some(d) = response.get("d") {
+            (d.get("results"), d.get("__next"))
+        } else {
+            (
+                response.get("value"),
+                response
+                    .get("@odata.nextLink")
+                    .or_else(|| response.get("odata.nextLink")),
+            )
+        };
+
+        let items = items
+            .and_then(Value::as_array)
+            .ok_or_else(|| AppError::Generic(t("error.sharepoint_no_items")))?;
+
+        let records = items
+            .iter()
+            .map(|item| Self::record_from_item(item, columns))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((records, next.and_then(Value::as_str).map(str::to_string)))
+    }
+
+    fn record_from_item(item: &Value, columns: &ColumnMapping) -> Result<SharePointRecord> {
+        let mut record = SharePointRecord::default();
+        let mut found = Vec::new();
+        let fields = item.get("fields").unwrap_or(item);
+
+        if let Some(fields) = fields.as_object() {
+            for (name, value) in fields {
+                if let Some(field) = columns.field_for(name) {
+                    record.set(field, Self::field_to_text(value));
+                    found.push(field);
+                }
+            }
+        }
+
+        if let Some(editor) = item.pointer("/lastModifiedBy/user/displayName").and_then(Value::as_str) {
+            record.set(SharePointField::ModificadoPor, Some(editor.to_string()));
+        }
+
+        ColumnMapping::check_missing(
+            SharePointField::ALL
+                .iter()
+                .filter(|field| field.is_required() && !found.contains(field)),
+        )?;
+        Ok(record)
+    }
+
+    /// Converte o valor de um campo para texto, como na exportação CSV
+    fn field_to_text(value: &Value) -> Option<String> {
+        match value {
+            Value::Null => None,
+            Value::String(s) => Some(s.clone()),
+            Value::Bool(b) => Some(if *b { "Sim" } else { "Não" }.to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            Value::Array(values) => {
+                let parts: Vec<String> = values.iter().filter_map(Self::field_to_text).collect();
+                Some(parts.join(", "))
+            }
+            // Campos de pesquisa e hiperlink
+            Value::Object(obj) => ["Url", "LookupValue", "Title", "Email"]
+                .iter()
+                .find_map(|key| obj.get(*key).and_then(Value::as_str))
+                .map(str::to_string),
+        }
+    }
+}
+
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~'".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::services::mock_server::MockServer;
+
+    fn fixture(name: &str) -> String {
+        let path = format!("{}/tests/fixtures/sharepoint_api/{}", env!("CARGO_MANIFEST_DIR"), name);
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn test_rest_paging() {
+        let server = MockServer::start(vec![
+            fixture("rest_page1.json"),
+            fixture("rest_page2.json"),
+        ]);
+
+        let client = SharePointClient::new(SharePointApi::Rest, &server.url, "Estações")
+            .with_token("tok")
+            .with_page_size(2);
+        let records = client.fetch_records().unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].id_rede, "RFeye002300");
+        assert_eq!(records[0].situacao, "Ativo");
+        assert_eq!(records[0].uf.as_deref(), Some("SP"));
+        assert_eq!(records[0].patrimonio.as_deref(), Some("123"));
+        assert_eq!(records[0].modificado_por.as_deref(), Some("Fulano"));
+        assert_eq!(records[2].situacao, "Disponível");
+
+        let requests = server.finish();
+        assert!(requests[0].request_line.contains("getbytitle('Esta%C3%A7%C3%B5es')"));
+        assert!(requests[0].request_line.contains("ID_x0020_de_x0020_rede"));
+        assert_eq!(requests[0].header("Authorization"), Some("Bearer tok"));
+        assert!(requests[1].request_line.contains("skiptoken"));
+    }
+
+    #[test]
+    fn test_select_configured_columns() {
+        let mut config = Config::default();
+        config.sharepoint.columns.insert("Estação".to_string(), SharePointField::Id);
+        let client = SharePointClient::new(SharePointApi::Graph, "https://graph/sites/x", "lista")
+            .with_columns(ColumnMapping::from_config(&config));
+
+        let url = client.first_page_url();
+        assert!(url.contains("ID_x0020_de_x0020_rede,"));
+        assert!(url.contains(",Esta_x00e7__x00e3_o,"));
+    }
+
+    #[test]
+    fn test_saved_graph_response() {
+        let records = SharePointClient::parse_saved_response(&fixture("graph_items.json"), &ColumnMapping::default()).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].id_rede, "CWSM212030");
+        assert_eq!(records[1].detentor.as_deref(), Some("GR02"));
+    }
+}
@@ -0,0 +1,274 @@
+//! Limites de `compare --fail-on`, para pipelines agendados: cada limite
+//! violado tem um código de saída próprio e o resultado vai numa linha
+//! JSON no stderr.
+//!
+//! Códigos: 3 para `any`; 10 a 18 para as categorias, na ordem de
+//! `DiscrepancyCategory` (`status` = 10 ... `only_in_sharepoint` = 18).
+//! Com vários limites violados, vale o primeiro na ordem informada.
+
+use crate::{
+    error::Result,
+    i18n::tf,
+    reports::json::SCHEMA_VERSION,
+    services::comparator::{ComparisonResult, DiscrepancyCategory},
+    utils::choice::Choice,
+};
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// Código de saída de `any`
+pub const EXIT_ANY: u8 = 3;
+/// Código da primeira categoria; as demais seguem em sequência
+pub const EXIT_CATEGORY_BASE: u8 = 10;
+
+/// Falha quando as discrepâncias de uma categoria (ou de todas) passam de
+/// `max`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Threshold {
+    /// `None` soma todas as categorias
+    pub category: Option<DiscrepancyCategory>,
+    pub max: usize,
+}
+
+impl Threshold {
+    pub fn exit_code(&self) -> u8 {
+        match self.category {
+            None => EXIT_ANY,
+            Some(category) => {
+                let index = DiscrepancyCategory::ALL
+                    .iter()
+                    .position(|c| *c == category)
+                    .unwrap_or_default();
+                EXIT_CATEGORY_BASE + index as u8
+            }
+        }
+    }
+
+    fn count(&self, counts: &IndexMap<DiscrepancyCategory, usize>) -> usize {
+        match self.category {
+            None => counts.values().sum(),
+            Some(category) => counts.get(&category).copied().unwrap_or(0),
+        }
+    }
+}
+
+/// `any`, `status`, `only_in_zabbix>5` (aceita também `only-in-zabbix`)
+impl FromStr for Threshold {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, String> {
+        let invalid = || tf("threshold.invalid", &[("value", &value)]);
+        let (name, max) = match value.split_once('>') {
+            Some((name, max)) => (name.trim(), max.trim().parse().map_err(|_| invalid())?),
+            None => (value.trim(), 0),
+        };
+        let category = if name.eq_ignore_ascii_case("any") {
+            None
+        } else {
+            let category = DiscrepancyCategory::from_name(&name.replace('-', "_")).ok_or_else(invalid)?;
+            Some(category)
+        };
+        Ok(Self { category, max })
+    }
+}
+
+impl fmt::Display for Threshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.category.map(Choice::name).unwrap_or("any");
+        if self.max == 0 {
+            write!(f, "{}", name)
+        } else {
+            write!(f, "{}>{}", name, self.max)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub rule: String,
+    pub count: usize,
+    pub max: usize,
+    pub exit_code: u8,
+}
+
+/// Resultado da verificação dos limites sobre as discrepâncias em aberto
+#[derive(Debug, Serialize)]
+pub struct ThresholdCheck {
+    pub schema_version: u32,
+    pub kind: &'static str,
+    /// "pass" ou "fail"
+    pub result: &'static str,
+    pub exit_code: u8,
+    pub total: usize,
+    pub counts: IndexMap<DiscrepancyCategory, usize>,
+    pub violations: Vec<Violation>,
+}
+
+impl ThresholdCheck {
+    pub fn evaluate(comparison: &ComparisonResult, thresholds: &[Threshold]) -> Self {
+        let counts = comparison.category_counts();
+        let violations: Vec<Violation> = thresholds
+            .iter()
+            .map(|threshold| (threshold, threshold.count(&counts)))
+            .filter(|(threshold, count)| *count > threshold.max)
+            .map(|(threshold, count)| Violation {
+                rule: threshold.to_string(),
+                count,
+                max: threshold.max,
+                exit_code: threshold.exit_code(),
+            })
+            .collect();
+        let exit_code = violations.first().map(|v| v.exit_code).unwrap_or(0);
+
+        Self {
+            schema_version: SCHEMA_VERSION,
+            kind: "fail_on",
+            result: if violations.is_empty() { "pass" } else { "fail" },
+            exit_code,
+            total: counts.values().sum(),
+            counts,
+            violations,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Resumo numa única linha JSON, para o stderr
+    pub fn summary_line(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::common::{estacao, DataSource, Status};
+    use crate::services::comparator::Comparator;
+
+    /// Uma divergência de status e duas estações só no Zabbix
+    fn comparison() -> ComparisonResult {
+        Comparator::compare(
+            vec![
+                estacao("RFeye002300", Status::Defeito, Some("GR01"), DataSource::Zabbix),
+                estacao("RFeye002301", Status::Ativo, Some("GR01"), DataSource::Zabbix),
+                estacao("RFeye002302", Status::Ativo, Some("GR01"), DataSource::Zabbix),
+            ],
+            vec![estacao("RFeye002300", Status::Ativo, Some("GR01"), DataSource::SharePoint)],
+            &Config::default(),
+        )
+    }
+
+    fn parse(values: &[&str]) -> Vec<Threshold> {
+        values.iter().map(|value| value.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_parse_any() {
+        assert_eq!("any".parse::<Threshold>().unwrap(), Threshold { category: None, max: 0 });
+        assert_eq!(" ANY ".parse::<Threshold>().unwrap(), Threshold { category: None, max: 0 });
+        assert_eq!("any>4".parse::<Threshold>().unwrap(), Threshold { category: None, max: 4 });
+        assert_eq!("any>4".parse::<Threshold>().unwrap().to_string(), "any>4");
+    }
+
+    #[test]
+    fn test_parse_category() {
+        let status: Threshold = "status".parse().unwrap();
+        assert_eq!(status, Threshold { category: Some(DiscrepancyCategory::Status), max: 0 });
+        assert_eq!(status.to_string(), "status");
+
+        // Nomes com hífen ou em maiúsculas equivalem ao nome canônico
+        for value in ["broken_link", "broken-link", "Broken-Link"] {
+            let threshold: Threshold = value.parse().unwrap();
+            assert_eq!(threshold.category, Some(DiscrepancyCategory::BrokenLink));
+            assert_eq!(threshold.to_string(), "broken_link");
+        }
+    }
+
+    #[test]
+    fn test_parse_category_with_max() {
+        let only: Threshold = "only-in-zabbix > 1".parse().unwrap();
+        assert_eq!(only, Threshold { category: Some(DiscrepancyCategory::OnlyInZabbix), max: 1 });
+        assert_eq!(only.to_string(), "only_in_zabbix>1");
+        assert_eq!(only.to_string().parse::<Threshold>().unwrap(), only);
+        // `>0` é o mesmo que nenhum máximo
+        assert_eq!("ip>0".parse::<Threshold>().unwrap().to_string(), "ip");
+    }
+
+    #[test]
+    fn test_invalid_specs() {
+        let values = ["", "statuss", "any>x", "status>", ">3", "status>-1", "status>1.5", "status<2", "only in zabbix"];
+        for value in values {
+            let error = value.parse::<Threshold>().unwrap_err();
+            assert!(error.contains(value), "{:?}: {}", value, error);
+        }
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!("any".parse::<Threshold>().unwrap().exit_code(), EXIT_ANY);
+        let codes: Vec<u8> = DiscrepancyCategory::ALL
+            .iter()
+            .map(|category| Threshold { category: Some(*category), max: 0 }.exit_code())
+            .collect();
+        assert_eq!(codes, (10..=18).collect::<Vec<u8>>());
+        assert_eq!("status".parse::<Threshold>().unwrap().exit_code(), 10);
+        assert_eq!("only_in_zabbix".parse::<Threshold>().unwrap().exit_code(), 17);
+    }
+
+    #[test]
+    fn test_first_given_wins() {
+        let comparison = comparison();
+
+        // `regional` não é violado e não conta para a ordem
+        let check = ThresholdCheck::evaluate(&comparison, &parse(&["regional", "only_in_zabbix>1", "any", "status"]));
+        assert_eq!(check.exit_code, 17);
+        let rules: Vec<&str> = check.violations.iter().map(|v| v.rule.as_str()).collect();
+        assert_eq!(rules, ["only_in_zabbix>1", "any", "status"]);
+
+        let check = ThresholdCheck::evaluate(&comparison, &parse(&["status", "any", "only_in_zabbix>1"]));
+        assert_eq!(check.exit_code, 10);
+        let check = ThresholdCheck::evaluate(&comparison, &parse(&["any", "status"]));
+        assert_eq!(check.exit_code, EXIT_ANY);
+    }
+
+    #[test]
+    fn test_repeated_category() {
+        // O mesmo limite repetido com máximos diferentes: cada um é avaliado
+        let thresholds = parse(&["only_in_zabbix>5", "only_in_zabbix>1", "only_in_zabbix"]);
+        let check = ThresholdCheck::evaluate(&comparison(), &thresholds);
+        let rules: Vec<&str> = check.violations.iter().map(|v| v.rule.as_str()).collect();
+        assert_eq!(rules, ["only_in_zabbix>1", "only_in_zabbix"]);
+        assert!(check.violations.iter().all(|v| v.count == 2 && v.exit_code == 17));
+    }
+
+    #[test]
+    fn test_thresholds() {
+        let any: Threshold = "any".parse().unwrap();
+        let only: Threshold = "only-in-zabbix > 1".parse().unwrap();
+        let comparison = comparison();
+
+        let check = ThresholdCheck::evaluate(&comparison, &[only, "regional".parse().unwrap(), any]);
+        assert!(!check.passed());
+        assert_eq!(check.exit_code, 17);
+        assert_eq!(check.violations.len(), 2);
+        assert_eq!(check.violations[1].rule, "any");
+        let line = check.summary_line().unwrap();
+        assert!(!line.contains('\n'));
+        assert!(line.contains("\"only_in_zabbix\":2"));
+        assert!(line.contains("\"result\":\"fail\""));
+
+        let check = ThresholdCheck::evaluate(&comparison, &["only_in_zabbix>2".parse().unwrap()]);
+        assert!(check.passed());
+        assert_eq!(check.exit_code, 0);
+        assert_eq!(check.result, "pass");
+
+        let check = ThresholdCheck::evaluate(&comparison, &[]);
+        assert!(check.passed());
+        assert_eq!(check.total, 3);
+    }
+}
@@ -0,0 +1,242 @@
+//! Séries temporais de contagem de estações por status e regional,
+//! montadas a partir dos snapshots do histórico.
+
+use crate::{
+    error::{AppError, Result},
+    i18n::t,
+    models::common::{DataSource, Status},
+    services::{aggregator::AggregatedData, aggregator::Aggregator, filter::StationFilter, history::SnapshotStore},
+    utils::choice::Choice,
+};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Regional usada nas séries com o total do status
+pub const ALL_REGIONALS: &str = "TOTAL";
+const SPARK_LEVELS: &[char] = &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Granularidade da série: todos os snapshots ou o último de cada período
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrendPeriod {
+    #[default]
+    All,
+    Daily,
+    Monthly,
+}
+
+impl Choice for TrendPeriod {
+    const ALL: &'static [Self] = &[TrendPeriod::All, TrendPeriod::Daily, TrendPeriod::Monthly];
+
+    fn name(self) -> &'static str {
+        match self {
+            TrendPeriod::All => "all",
+            TrendPeriod::Daily => "daily",
+            TrendPeriod::Monthly => "monthly",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendSeries {
+    pub status: Status,
+    pub regional: String,
+    /// Uma contagem por data de `Trend::dates`
+    pub counts: Vec<usize>,
+}
+
+impl TrendSeries {
+    pub fn latest(&self) -> usize {
+        self.counts.last().copied().unwrap_or(0)
+    }
+
+    /// Variação em relação ao ponto anterior
+    pub fn delta(&self) -> i64 {
+        match self.counts.as_slice() {
+            [.., previous, last] => *last as i64 - *previous as i64,
+            _ => 0,
+        }
+    }
+
+    /// Variação desde o primeiro ponto
+    pub fn change(&self) -> i64 {
+        self.latest() as i64 - self.counts.first().copied().unwrap_or(0) as i64
+    }
+
+    pub fn sparkline(&self) -> String {
+        let min = self.counts.iter().copied().min().unwrap_or(0);
+        let max = self.counts.iter().copied().max().unwrap_or(0);
+        self.counts
+            .iter()
+            .map(|&count| {
+                if max == min {
+                    return if max == 0 { SPARK_LEVELS[0] } else { SPARK_LEVELS[3] };
+                }
+                let level = (count - min) * (SPARK_LEVELS.len() - 1) / (max - min);
+                SPARK_LEVELS[level]
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Trend {
+    pub dates: Vec<DateTime<Local>>,
+    pub series: Vec<TrendSeries>,
+}
+
+impl Trend {
+    /// Lê os snapshots do histórico e agrega as estações da origem
+    /// escolhida que atendem ao recorte
+    pub fn from_store(
+        store: &SnapshotStore,
+        source: DataSource,
+        period: TrendPeriod,
+        filter: &StationFilter,
+    ) -> Result<Self> {
+        let ids = Self::select(store.list()?, period);
+        if ids.is_empty() {
+            return Err(AppError::Generic(t("history.empty")));
+        }
+
+        let mut points = Vec::with_capacity(ids.len());
+        for id in &ids {
+            let snapshot = store.load(id)?;
+            let estacoes = match source {
+                DataSource::Zabbix => snapshot.zabbix,
+                DataSource::SharePoint => snapshot.sharepoint,
+            };
+            let estacoes = filter.apply(estacoes);
+            points.push((snapshot.created_at, Aggregator::group_by_status_and_regional(&estacoes)));
+        }
+        Ok(Self::from_aggregated(&points))
+    }
+
+    /// Mantém o último snapshot de cada dia ou mês. Os IDs começam por
+    /// `AAAAMMDD`, então basta comparar os prefixos.
+    fn select(ids: Vec<String>, period: TrendPeriod) -> Vec<String> {
+        let prefix = match period {
+            TrendPeriod::All => return ids,
+            TrendPeriod::Daily => 8,
+            TrendPeriod::Monthly => 6,
+        };
+        let mut by_period: BTreeMap<String, String> = BTreeMap::new();
+        for id in ids {
+            by_period.insert(id.chars().take(prefix).collect(), id);
+        }
+        by_period.into_values().collect()
+    }
+
+    pub fn from_aggregated(points: &[(DateTime<Local>, AggregatedData)]) -> Self {
+        let mut series: BTreeMap<(Status, String), Vec<usize>> = BTreeMap::new();
+
+        for (index, (_, data)) in points.iter().enumerate() {
+            for ((status, regional), estacoes) in &data.by_status_and_regional {
+                series
+                    .entry((status.clone(), regional.clone()))
+                    .or_insert_with(|| vec![0; points.len()])[index] = estacoes.len();
+            }
+            for (status, count) in &data.totals.by_status {
+                series
+                    .entry((status.clone(), ALL_REGIONALS.to_string()))
+                    .or_insert_with(|| vec![0; points.len()])[index] = *count;
+            }
+        }
+
+        Self {
+            dates: points.iter().map(|(date, _)| *date).collect(),
+            series: series
+                .into_iter()
+                .map(|((status, regional), counts)| TrendSeries {
+                    status,
+                    regional,
+                    counts,
+                })
+                .collect(),
+        }
+    }
+
+    /// CSV em formato longo (data, status, regional, total), pronto para
+    /// tabelas dinâmicas e gráficos
+    pub fn to_csv(&self) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(["data", "status", "regional", "total"])?;
+        for series in &self.series {
+            for (date, count) in self.dates.iter().zip(&series.counts) {
+                writer.write_record([
+                    date.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    series.status.to_string(),
+                    series.regional.clone(),
+                    count.to_string(),
+                ])?;
+            }
+        }
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| AppError::Generic(e.to_string()))?;
+        Ok(String::from_utf8_lossy(&bytes).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::common::estacao;
+    use crate::services::comparator::Comparator;
+    use crate::services::history::Snapshot;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_series_with_deltas() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SnapshotStore::new(dir.path());
+        let day = |d| Local.with_ymd_and_hms(2026, 1, d, 8, 0, 0).unwrap();
+        let zabbix = |status: Status, regional| estacao("RFeye002300", status, Some(regional), DataSource::Zabbix);
+        for (date, estacoes) in [
+            (day(1), vec![
+                zabbix(Status::Defeito, "GR01"),
+                zabbix(Status::Defeito, "GR01"),
+                zabbix(Status::Defeito, "GR02"),
+            ]),
+            (day(2), vec![zabbix(Status::Defeito, "GR01"), zabbix(Status::Ativo, "GR02")]),
+        ] {
+            let result = Comparator::compare(Vec::new(), Vec::new(), &Config::default());
+            let mut snapshot = Snapshot::new(estacoes, Vec::new(), result);
+            snapshot.created_at = date;
+            store.save(&snapshot).unwrap();
+        }
+
+        let filter = StationFilter {
+            regionals: vec!["gr01".to_string()],
+            statuses: vec![Status::Defeito],
+            ..Default::default()
+        };
+        let trend = Trend::from_store(&store, DataSource::Zabbix, TrendPeriod::All, &filter).unwrap();
+
+        let gr01 = trend.series.iter().find(|s| s.regional == "GR01").unwrap();
+        assert_eq!(gr01.counts, vec![2, 1]);
+        assert_eq!(gr01.delta(), -1);
+        assert_eq!(gr01.sparkline(), "█▁");
+        // O total também só conta as estações do recorte
+        let total = trend.series.iter().find(|s| s.regional == ALL_REGIONALS).unwrap();
+        assert_eq!(total.counts, vec![2, 1]);
+        assert_eq!(trend.series.len(), 2);
+
+        let csv = trend.to_csv().unwrap();
+        assert!(csv.starts_with("data,status,regional,total\n2026-01-01 08:00:00,Defeito,GR01,2\n"));
+    }
+
+    #[test]
+    fn test_select_last_of_each_month() {
+        let ids = vec![
+            "20260105-080000".to_string(),
+            "20260131-080000".to_string(),
+            "20260210-080000".to_string(),
+        ];
+        assert_eq!(
+            Trend::select(ids, TrendPeriod::Monthly),
+            vec!["20260131-080000".to_string(), "20260210-080000".to_string()]
+        );
+    }
+}
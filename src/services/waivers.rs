@@ -0,0 +1,366 @@
+//! Isenções: discrepâncias conhecidas e aceitas até uma data.
+//!
+//! ```toml
+//! [[waiver]]
+//! station = "RFeye002300"
+//! category = "status"          # ou field = "ip"
+//! justification = "Em litígio; mantida como Defeito no Zabbix"
+//! owner = "Fulano"
+//! expires = "2026-12-31"
+//! ```
+
+use crate::{
+    config::Config,
+    error::{AppError, Result},
+    i18n::tf,
+    models::{common::EstacaoInfo, compare::ComparableField},
+    services::comparator::{
+        status_description, BrokenLink, ComparisonResult, DiscrepancyCategory, Duplicate, FieldDiff, IpIssue,
+        ProbableMatch, RegionalMismatch, StatusMismatch,
+    },
+    utils::normalizer::Normalizer,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Waivers {
+    #[serde(default, rename = "waiver")]
+    pub entries: Vec<Waiver>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Waiver {
+    /// ID ou nome da estação (comparado após a normalização)
+    pub station: String,
+    #[serde(default)]
+    pub category: Option<DiscrepancyCategory>,
+    /// Restringe a isenção a um campo; sem `category`, a categoria é a
+    /// do campo
+    #[serde(default)]
+    pub field: Option<ComparableField>,
+    pub justification: String,
+    pub owner: String,
+    /// Último dia de validade
+    pub expires: NaiveDate,
+}
+
+impl Waiver {
+    pub fn category(&self) -> DiscrepancyCategory {
+        self.category
+            .or(self.field.map(DiscrepancyCategory::for_field))
+            .unwrap_or(DiscrepancyCategory::Field)
+    }
+
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.expires < today
+    }
+}
+
+/// Discrepância retirada do relatório por uma isenção
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcceptedItem {
+    pub category: DiscrepancyCategory,
+    pub station: String,
+    #[serde(alias = "detail")]
+    pub discrepancy: AcceptedDiscrepancy,
+    pub waiver: Waiver,
+}
+
+impl AcceptedItem {
+    /// Descrição no idioma corrente
+    pub fn detail(&self) -> String {
+        match &self.discrepancy {
+            AcceptedDiscrepancy::Status(m) => m.description(),
+            AcceptedDiscrepancy::Regional(m) => m.description(),
+            AcceptedDiscrepancy::Field(f) => f.description(),
+            AcceptedDiscrepancy::BrokenLink(l) => l.description(),
+            AcceptedDiscrepancy::Ip(i) => i.description(),
+            AcceptedDiscrepancy::Duplicate(d) => d.description(),
+            AcceptedDiscrepancy::ProbableMatch(m) => m.description(),
+            AcceptedDiscrepancy::OnlyIn(e) => status_description(e),
+            AcceptedDiscrepancy::Text(text) => text.clone(),
+        }
+    }
+}
+
+/// Registro da discrepância aceita, traduzido só na exibição
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcceptedDiscrepancy {
+    Status(StatusMismatch),
+    Regional(RegionalMismatch),
+    Field(FieldDiff),
+    BrokenLink(BrokenLink),
+    Ip(IpIssue),
+    Duplicate(Duplicate),
+    ProbableMatch(Box<ProbableMatch>),
+    /// Estação presente em só uma das origens
+    OnlyIn(Box<EstacaoInfo>),
+    /// Texto já pronto, dos snapshots anteriores
+    #[serde(untagged)]
+    Text(String),
+}
+
+impl Waivers {
+    /// Lê o arquivo; um arquivo inexistente equivale a nenhuma isenção
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| AppError::Config(format!("{}: {}", path.display(), e)))?;
+        Self::from_toml_str(&content)
+            .map_err(|e| AppError::Config(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        let waivers: Self = toml::from_str(content).map_err(|e| AppError::Config(e.to_string()))?;
+        if let Some(waiver) = waivers
+            .entries
+            .iter()
+            .find(|w| w.category.is_none() && w.field.is_none())
+        {
+            return Err(AppError::Config(tf("error.waiver_scope", &[("station", &waiver.station)])));
+        }
+        Ok(waivers)
+    }
+
+    /// Move para `accepted` as discrepâncias cobertas por isenções válidas.
+    /// As cobertas apenas por isenções vencidas continuam no resultado e
+    /// as isenções vão para `expired_waivers`.
+    pub fn apply(&self, comparison: &mut ComparisonResult, today: NaiveDate, config: &Config) {
+        let mut applier = Applier {
+            waivers: self,
+            today,
+            config,
+            accepted: Vec::new(),
+            expired: Vec::new(),
+        };
+
+        comparison.status_mismatch.retain(|m| {
+            !applier.accept(
+                DiscrepancyCategory::Status,
+                Some(ComparableField::Status),
+                &[&m.nome, &m.id],
+                || AcceptedDiscrepancy::Status(m.clone()),
+            )
+        });
+        comparison.regional_mismatch.retain(|m| {
+            !applier.accept(
+                DiscrepancyCategory::Regional,
+                Some(ComparableField::Regional),
+                &[&m.nome, &m.id],
+                || AcceptedDiscrepancy::Regional(m.clone()),
+            )
+        });
+
+        for diff in &mut comparison.diffs {
+            let stations = [diff.nome.as_str(), diff.id.as_str()];
+            diff.fields.retain(|f| {
+                let category = DiscrepancyCategory::for_field(f.field);
+                // Status e regional já foram registrados nas listas próprias
+                if category != DiscrepancyCategory::Field {
+                    return applier.find(category, Some(f.field), &stations).is_none();
+                }
+                !applier.accept(category, Some(f.field), &stations, || AcceptedDiscrepancy::Field(f.clone()))
+            });
+        }
+        comparison.diffs.retain(|d| !d.fields.is_empty());
+
+        comparison.broken_links.retain(|l| {
+            !applier.accept(DiscrepancyCategory::BrokenLink, None, &[&l.id], || {
+                AcceptedDiscrepancy::BrokenLink(l.clone())
+            })
+        });
+        comparison.ip_issues.retain(|i| {
+            !applier.accept(DiscrepancyCategory::Ip, None, &[&i.nome, &i.id], || {
+                AcceptedDiscrepancy::Ip(i.clone())
+            })
+        });
+        comparison.duplicates.retain(|d| {
+            !applier.accept(DiscrepancyCategory::Duplicate, None, &[&d.key], || {
+                AcceptedDiscrepancy::Duplicate(d.clone())
+            })
+        });
+        comparison.probable_matches.retain(|m| {
+            !applier.accept(
+                DiscrepancyCategory::ProbableMatch,
+                None,
+                &[&m.zabbix.nome, &m.sharepoint.id],
+                || AcceptedDiscrepancy::ProbableMatch(Box::new(m.clone())),
+            )
+        });
+        comparison.only_in_zabbix.retain(|e| {
+            !applier.accept(DiscrepancyCategory::OnlyInZabbix, None, &[&e.nome, &e.id], || {
+                AcceptedDiscrepancy::OnlyIn(Box::new(e.clone()))
+            })
+        });
+        comparison.only_in_sharepoint.retain(|e| {
+            !applier.accept(DiscrepancyCategory::OnlyInSharepoint, None, &[&e.id], || {
+                AcceptedDiscrepancy::OnlyIn(Box::new(e.clone()))
+            })
+        });
+
+        comparison.accepted.extend(applier.accepted);
+        comparison.expired_waivers.extend(applier.expired);
+    }
+}
+
+struct Applier<'a> {
+    waivers: &'a Waivers,
+    today: NaiveDate,
+    config: &'a Config,
+    accepted: Vec<AcceptedItem>,
+    expired: Vec<Waiver>,
+}
+
+impl Applier<'_> {
+    /// Isenção válida para a discrepância; as vencidas que a cobririam
+    /// são anotadas
+    fn find(
+        &mut self,
+        category: DiscrepancyCategory,
+        field: Option<ComparableField>,
+        stations: &[&str],
+    ) -> Option<Waiver> {
+        let keys: Vec<String> = stations
+            .iter()
+            .map(|s| Normalizer::normalize_id(s, self.config))
+            .collect();
+
+        let mut valid = None;
+        for waiver in &self.waivers.entries {
+            let matches = waiver.category() == category
+                && (waiver.field.is_none() || waiver.field == field)
+                && keys.contains(&Normalizer::normalize_id(&waiver.station, self.config));
+            if !matches {
+                continue;
+            }
+            if waiver.is_expired(self.today) {
+                if !self.expired.contains(waiver) {
+                    self.expired.push(waiver.clone());
+                }
+            } else if valid.is_none() {
+                valid = Some(waiver.clone());
+            }
+        }
+        valid
+    }
+
+    fn accept(
+        &mut self,
+        category: DiscrepancyCategory,
+        field: Option<ComparableField>,
+        stations: &[&str],
+        discrepancy: impl FnOnce() -> AcceptedDiscrepancy,
+    ) -> bool {
+        match self.find(category, field, stations) {
+            Some(waiver) => {
+                self.accepted.push(AcceptedItem {
+                    category,
+                    station: stations[0].to_string(),
+                    discrepancy: discrepancy(),
+                    waiver,
+                });
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::common::{estacao, DataSource, Status};
+    use crate::services::comparator::Comparator;
+
+    #[test]
+    fn test_waivers_until_expiry() {
+        let waivers = Waivers::from_toml_str(
+            r#"
+            [[waiver]]
+            station = "RFeye002300"
+            category = "status"
+            justification = "Litígio"
+            owner = "Fulano"
+            expires = "2026-06-30"
+
+            [[waiver]]
+            station = "rfeye2301"
+            field = "ip"
+            justification = "Troca de VPN agendada"
+            owner = "Beltrano"
+            expires = "2026-01-31"
+            "#,
+        )
+        .unwrap();
+
+        let comparison = || {
+            let mut z2 = estacao("RFeye002301", Status::Ativo, Some("GR01"), DataSource::Zabbix);
+            z2.ip = Some("10.0.0.1".to_string());
+            let mut sp2 = estacao("RFeye002301", Status::Ativo, Some("GR01"), DataSource::SharePoint);
+            sp2.ip = Some("10.0.0.2".to_string());
+            Comparator::compare(
+                vec![estacao("RFeye002300", Status::Defeito, Some("GR01"), DataSource::Zabbix), z2],
+                vec![estacao("RFeye002300", Status::Litigio, Some("GR01"), DataSource::SharePoint), sp2],
+                &Config::default(),
+            )
+        };
+        let config = Config::default();
+
+        let mut result = comparison();
+        waivers.apply(&mut result, NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(), &config);
+        assert!(result.status_mismatch.is_empty());
+        assert!(result.diffs.is_empty());
+        assert_eq!(result.accepted.len(), 2);
+        assert!(result.expired_waivers.is_empty());
+
+        let mut result = comparison();
+        waivers.apply(&mut result, NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(), &config);
+        assert!(result.status_mismatch.is_empty());
+        assert_eq!(result.diffs.len(), 1);
+        assert_eq!(result.diffs[0].fields[0].field, ComparableField::Ip);
+        assert_eq!(result.expired_waivers.len(), 1);
+        assert_eq!(result.expired_waivers[0].owner, "Beltrano");
+    }
+
+    #[test]
+    fn test_accepted_detail_from_old_snapshot() {
+        let item: AcceptedItem = serde_json::from_value(serde_json::json!({
+            "category": "status",
+            "station": "RFeye002300",
+            "detail": "Zabbix Defeito | SharePoint Litígio",
+            "waiver": {
+                "station": "RFeye002300",
+                "category": "status",
+                "justification": "Litígio",
+                "owner": "Fulano",
+                "expires": "2026-06-30"
+            }
+        }))
+        .unwrap();
+        assert_eq!(item.detail(), "Zabbix Defeito | SharePoint Litígio");
+
+        let json = serde_json::to_value(&item).unwrap();
+        let item: AcceptedItem = serde_json::from_value(json).unwrap();
+        assert!(matches!(item.discrepancy, AcceptedDiscrepancy::Text(_)));
+    }
+
+    #[test]
+    fn test_waiver_needs_category_or_field() {
+        let err = Waivers::from_toml_str(
+            r#"
+            [[waiver]]
+            station = "RFeye002300"
+            justification = "x"
+            owner = "y"
+            expires = "2026-06-30"
+            "#,
+        );
+        assert!(err.is_err());
+    }
+}
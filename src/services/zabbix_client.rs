@@ -0,0 +1,300 @@
+use crate::{
+    config::ZABBIX_PAGE_SIZE,
+    error::{AppError, Result},
+    i18n::{t, tf},
+    models::zabbix::{RawZabbixHost, ZabbixHost, ZabbixVersion},
+};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::{debug, info};
+
+const API_PATH: &str = "api_jsonrpc.php";
+
+/// Credenciais aceitas pela API do Zabbix
+#[derive(Debug, Clone)]
+pub enum ZabbixAuth {
+    Token(String),
+    Password { user: String, password: String },
+}
+
+/// Cliente JSON-RPC para a API do Zabbix
+pub struct ZabbixClient {
+    endpoint: String,
+    agent: ureq::Agent,
+    page_size: usize,
+    version: Option<ZabbixVersion>,
+    auth: Option<String>,
+    session: bool,
+    next_id: u64,
+}
+
+impl ZabbixClient {
+    /// Aceita tanto a URL base do frontend quanto o caminho completo
+    /// de `api_jsonrpc.php`.
+    pub fn new(url: &str) -> Self {
+        let url = url.trim_end_matches('/');
+        let endpoint = if url.ends_with(".php") {
+            url.to_string()
+        } else {
+            format!("{}/{}", url, API_PATH)
+        };
+
+        Self {
+            endpoint,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(60))
+                .build(),
+            page_size: ZABBIX_PAGE_SIZE,
+            version: None,
+            auth: None,
+            session: false,
+            next_id: 1,
+        }
+    }
+
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Versão da API (`apiinfo.version`), consultada uma única vez
+    pub fn api_version(&mut self) -> Result<ZabbixVersion> {
+        if let Some(version) = self.version {
+            return Ok(version);
+        }
+
+        let raw: String = self.call("apiinfo.version", json!([]), false)?;
+        let version = ZabbixVersion::parse(&raw)
+            .ok_or_else(|| AppError::ZabbixApi(tf("error.zabbix_version", &[("version", &raw)])))?;
+        info!("{}", tf("log.zabbix_api_version", &[("version", &raw)]));
+
+        self.version = Some(version);
+        Ok(version)
+    }
+
+    pub fn login(&mut self, auth: &ZabbixAuth) -> Result<()> {
+        let version = self.api_version()?;
+
+        match auth {
+            ZabbixAuth::Token(token) => {
+                self.auth = Some(token.clone());
+                self.session = false;
+            }
+            ZabbixAuth::Password { user, password } => {
+                let user_key = if version.uses_username() { "username" } else { "user" };
+                let session: String = self.call(
+                    "user.login",
+                    json!({ user_key: user, "password": password }),
+                    false,
+                )?;
+                self.auth = Some(session);
+                self.session = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// Encerra a sessão aberta por `login` com usuário e senha
+    pub fn logout(&mut self) -> Result<()> {
+        if self.session {
+            let _: bool = self.call("user.logout", json!([]), true)?;
+            self.session = false;
+            self.auth = None;
+        }
+        Ok(())
+    }
+
+    /// Busca todos os hosts com seus grupos. Os IDs são listados primeiro e
+    /// os detalhes buscados em páginas de `page_size` hosts, já que
+    /// `host.get` não oferece paginação por deslocamento.
+    pub fn fetch_hosts(&mut self) -> Result<Vec<ZabbixHost>> {
+        let version = self.api_version()?;
+
+        let ids: Vec<Value> = self.call(
+            "host.get",
+            json!({ "output": ["hostid"], "sortfield": "hostid" }),
+            true,
+        )?;
+        let ids: Vec<String> = ids
+            .iter()
+            .filter_map(|v| v.get("hostid").and_then(Value::as_str))
+            .map(str::to_string)
+            .collect();
+        info!("{}", tf("log.zabbix_hosts", &[("count", &ids.len())]));
+
+        let select_key = if version.uses_hostgroups() {
+            "selectHostGroups"
+        } else {
+            "selectGroups"
+        };
+
+        let mut hosts = Vec::with_capacity(ids.len());
+        for (page, chunk) in ids.chunks(self.page_size).enumerate() {
+            debug!("Buscando página {} ({} hosts)", page + 1, chunk.len());
+            let page_hosts: Vec<RawZabbixHost> = self.call(
+                "host.get",
+                json!({
+                    "output": ["hostid", "host", "name"],
+                    "hostids": chunk,
+                    select_key: ["groupid", "name"],
+                    "selectInterfaces": ["ip", "dns", "port", "type", "main"],
+                    "sortfield": "hostid",
+                }),
+                true,
+            )?;
+            hosts.extend(page_hosts.into_iter().map(|raw| raw.into_host(Some(&version))));
+        }
+
+        Ok(hosts)
+    }
+
+    fn call<T: DeserializeOwned>(&mut self, method: &str, params: Value, authenticated: bool) -> Result<T> {
+        let mut body = json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": self.next_id,
+        });
+        self.next_id += 1;
+
+        let mut request = self
+            .agent
+            .post(&self.endpoint)
+            .set("Content-Type", "application/json-rpc");
+
+        if authenticated {
+            let auth = self
+                .auth
+                .clone()
+                .ok_or_else(|| AppError::ZabbixApi(t("error.zabbix_not_authenticated")))?;
+            // O campo "auth" foi removido na 7.2
+            if self.version.is_some_and(|v| v.supports_bearer()) {
+                request = request.set("Authorization", &format!("Bearer {}", auth));
+            } else {
+                body["auth"] = Value::String(auth);
+            }
+        }
+
+        let response: Value = request
+            .send_json(body)
+            .map_err(|e| AppError::Http(e.to_string()))?
+            .into_json()?;
+
+        if let Some(error) = response.get("error") {
+            return Err(AppError::ZabbixApi(format!(
+                "{} ({}): {}",
+                error.get("message").and_then(Value::as_str).unwrap_or("error"),
+                method,
+                error.get("data").and_then(Value::as_str).unwrap_or(""),
+            )));
+        }
+
+        let result = response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| AppError::ZabbixApi(tf("error.zabbix_no_result", &[("method", &method)])))?;
+        Ok(serde_json::from_value(result)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::mock_server::MockServer;
+
+    fn fixture(name: &str) -> String {
+        let path = format!("{}/tests/fixtures/zabbix_api/{}", env!("CARGO_MANIFEST_DIR"), name);
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn test_password_login_and_paging() {
+        let server = MockServer::start(vec![
+            fixture("apiinfo_5.0.json"),
+            fixture("user_login.json"),
+            fixture("host_ids.json"),
+            fixture("hosts_page1_5.0.json"),
+            fixture("hosts_page2_5.0.json"),
+        ]);
+
+        let mut client = ZabbixClient::new(&server.url).with_page_size(2);
+        client
+            .login(&ZabbixAuth::Password {
+                user: "api".to_string(),
+                password: "secret".to_string(),
+            })
+            .unwrap();
+        let hosts = client.fetch_hosts().unwrap();
+
+        assert_eq!(hosts.len(), 3);
+        assert_eq!(hosts[2].name, "CWSM212030");
+        assert_eq!(hosts[0].groups[0].groupid, "54");
+
+        let requests = server.finish();
+        assert_eq!(requests.len(), 5);
+        assert!(requests[0].request_line.contains("/api_jsonrpc.php"));
+        assert!(requests[1].body.contains("\"user\":\"api\""));
+        assert!(requests[3].body.contains("\"auth\":\"0424bd59b807674191e7d77572075f33\""));
+        assert!(requests[3].body.contains("selectGroups"));
+        assert!(requests[4].body.contains("10103"));
+    }
+
+    #[test]
+    fn test_token_uses_bearer_header() {
+        let server = MockServer::start(vec![
+            fixture("apiinfo_6.4.json"),
+            fixture("host_ids.json"),
+            fixture("hosts_page1_5.0.json"),
+            fixture("hosts_page2_5.0.json"),
+        ]);
+
+        let mut client = ZabbixClient::new(&server.url).with_page_size(2);
+        client.login(&ZabbixAuth::Token("tok".to_string())).unwrap();
+        client.fetch_hosts().unwrap();
+
+        let requests = server.finish();
+        assert_eq!(requests[1].header("Authorization"), Some("Bearer tok"));
+        assert!(!requests[1].body.contains("\"auth\""));
+        assert!(requests[2].body.contains("selectHostGroups"));
+        assert!(requests[2].body.contains("selectInterfaces"));
+    }
+
+    #[test]
+    fn test_hostgroups_on_recent_versions() {
+        let server = MockServer::start(vec![
+            fixture("apiinfo_7.0.json"),
+            fixture("host_ids.json"),
+            fixture("hosts_7.0.json"),
+        ]);
+
+        let mut client = ZabbixClient::new(&server.url);
+        client.login(&ZabbixAuth::Token("tok".to_string())).unwrap();
+        let hosts = client.fetch_hosts().unwrap();
+
+        assert_eq!(hosts.len(), 3);
+        assert_eq!(hosts[1].groups[0].groupid, "48");
+        assert_eq!(hosts[0].main_ip(), Some("172.24.0.10"));
+        assert_eq!(hosts[2].main_ip(), None);
+        server.finish();
+    }
+
+    #[test]
+    fn test_api_error_is_reported() {
+        let server = MockServer::start(vec![
+            fixture("apiinfo_5.0.json"),
+            fixture("login_error.json"),
+        ]);
+
+        let mut client = ZabbixClient::new(&server.url);
+        let err = client
+            .login(&ZabbixAuth::Password {
+                user: "api".to_string(),
+                password: "wrong".to_string(),
+            })
+            .unwrap_err();
+
+        assert!(matches!(err, AppError::ZabbixApi(_)));
+        server.finish();
+    }
+}
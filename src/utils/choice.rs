@@ -0,0 +1,40 @@
+//! Enums escolhidos pelo nome na linha de comando. A biblioteca não
+//! depende do clap: o binário monta os valores aceitos a partir daqui.
+
+/// Enum com um nome fixo por variante
+pub trait Choice: Sized + Copy + 'static {
+    /// Todas as variantes, na ordem de exibição
+    const ALL: &'static [Self];
+
+    /// Nome aceito na linha de comando
+    fn name(self) -> &'static str;
+
+    /// Outros nomes aceitos
+    fn aliases(self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Variante pelo nome ou apelido, sem diferenciar maiúsculas
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        Self::ALL.iter().copied().find(|value| {
+            value.name().eq_ignore_ascii_case(name)
+                || value.aliases().iter().any(|alias| alias.eq_ignore_ascii_case(name))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Lang;
+    use crate::models::common::DataSource;
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(DataSource::from_name("share-point"), Some(DataSource::SharePoint));
+        assert_eq!(Lang::from_name("PT"), Some(Lang::PtBr));
+        assert_eq!(Lang::from_name("en"), Some(Lang::En));
+        assert_eq!(Lang::from_name("fr"), None);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod choice;
 pub mod normalizer;
 
-pub use normalizer::Normalizer;
\ No newline at end of file
+pub use choice::Choice;
+pub use normalizer::Normalizer;
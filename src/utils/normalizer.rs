@@ -1,24 +1,19 @@
+use crate::config::Config;
+
 pub struct Normalizer;
 
 impl Normalizer {
-    /// Normaliza um ID removendo prefixos e sufixos comuns
-    pub fn normalize_id(id: &str) -> String {
+    /// Normaliza um ID removendo os prefixos configurados por tipo de estação
+    pub fn normalize_id(id: &str, config: &Config) -> String {
         let id_lower = id.to_lowercase();
         
         // Remover prefixos comuns
-        let without_prefix = if id_lower.starts_with("rfeye") {
-            &id[5..]
-        } else if id_lower.starts_with("miaer-") {
-            &id[6..]
-        } else if id_lower.starts_with("cwsm") {
-            &id[4..]
-        } else if id_lower.starts_with("ums") {
-            &id[3..]
-        } else if id_lower.starts_with("ermx") {
-            &id[4..]
-        } else {
-            id
-        };
+        let without_prefix = config
+            .id_prefixes()
+            .map(str::to_lowercase)
+            .find(|prefix| id_lower.starts_with(prefix.as_str()))
+            .and_then(|prefix| id.get(prefix.len()..))
+            .unwrap_or(id);
         
         // Remover zeros à esquerda se for numérico
         if without_prefix.chars().all(|c| c.is_ascii_digit()) {
@@ -50,9 +45,9 @@ impl Normalizer {
     }
     
     /// Compara dois IDs de forma flexível
-    pub fn ids_match(id1: &str, id2: &str) -> bool {
-        let norm1 = Self::normalize_id(id1);
-        let norm2 = Self::normalize_id(id2);
+    pub fn ids_match(id1: &str, id2: &str, config: &Config) -> bool {
+        let norm1 = Self::normalize_id(id1, config);
+        let norm2 = Self::normalize_id(id2, config);
         
         // Comparação exata após normalização
         if norm1 == norm2 {
@@ -60,18 +55,33 @@ impl Normalizer {
         }
         
         // Tentar comparar apenas números se ambos tiverem
-        let num1 = norm1.chars()
-            .filter(|c| c.is_ascii_digit())
-            .collect::<String>();
-        let num2 = norm2.chars()
-            .filter(|c| c.is_ascii_digit())
-            .collect::<String>();
+        let num1 = Self::serial(&norm1);
         
-        if !num1.is_empty() && num1 == num2 {
-            return true;
+        num1.is_some() && num1 == Self::serial(&norm2)
+    }
+    
+    /// Número de série contido no ID (apenas os dígitos, sem zeros à
+    /// esquerda)
+    pub fn serial(id: &str) -> Option<String> {
+        let digits: String = id.chars().filter(|c| c.is_ascii_digit()).collect();
+        let serial = digits.trim_start_matches('0');
+        match (digits.is_empty(), serial.is_empty()) {
+            (true, _) => None,
+            (false, true) => Some("0".to_string()),
+            (false, false) => Some(serial.to_string()),
         }
-        
-        false
+    }
+    
+    /// Similaridade entre dois IDs (0 a 1) pela distância de edição,
+    /// ignorando maiúsculas e separadores
+    pub fn similarity(id1: &str, id2: &str) -> f64 {
+        let simplify = |id: &str| -> String {
+            id.chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+                .to_lowercase()
+        };
+        strsim::normalized_levenshtein(&simplify(id1), &simplify(id2))
     }
 }
 
@@ -81,10 +91,11 @@ mod tests {
     
     #[test]
     fn test_normalize_id() {
-        assert_eq!(Normalizer::normalize_id("RFeye002300"), "2300");
-        assert_eq!(Normalizer::normalize_id("MIAer-SP"), "miaer-sp");
-        assert_eq!(Normalizer::normalize_id("CWSM212030"), "212030");
-        assert_eq!(Normalizer::normalize_id("00123"), "123");
+        let config = Config::default();
+        assert_eq!(Normalizer::normalize_id("RFeye002300", &config), "2300");
+        assert_eq!(Normalizer::normalize_id("MIAer-SP", &config), "miaer-sp");
+        assert_eq!(Normalizer::normalize_id("CWSM212030", &config), "212030");
+        assert_eq!(Normalizer::normalize_id("00123", &config), "123");
     }
     
     #[test]
@@ -96,9 +107,10 @@ mod tests {
     
     #[test]
     fn test_ids_match() {
-        assert!(Normalizer::ids_match("RFeye002300", "rfeye002300"));
-        assert!(Normalizer::ids_match("RFeye002300", "2300"));
-        assert!(Normalizer::ids_match("MIAer-SP", "miaer-sp"));
-        assert!(!Normalizer::ids_match("RFeye002300", "RFeye002301"));
+        let config = Config::default();
+        assert!(Normalizer::ids_match("RFeye002300", "rfeye002300", &config));
+        assert!(Normalizer::ids_match("RFeye002300", "2300", &config));
+        assert!(Normalizer::ids_match("MIAer-SP", "miaer-sp", &config));
+        assert!(!Normalizer::ids_match("RFeye002300", "RFeye002301", &config));
     }
 }
\ No newline at end of file